@@ -0,0 +1,80 @@
+use std::path::PathBuf;
+
+use crate::theme::Theme;
+
+/// The command line options
+#[derive(Debug)]
+pub struct Args {
+    pub theme: Theme,
+    /// A file to load key bindings from
+    pub config: Option<PathBuf>,
+    /// Play a game from the keyboard instead of running the demo
+    pub play: bool,
+    /// A GIF file to record every board to
+    pub record: Option<PathBuf>,
+    /// How long each recorded board is shown, in milliseconds
+    pub frame_delay: u16,
+    /// Print a report on the game once it's over
+    pub stats: bool,
+}
+
+impl Args {
+    /// Parses the options in `args`, not including the program name
+    ///
+    /// `--plain` always wins over `--theme`, and the fancy theme falls back
+    /// to plain if the terminal doesn't look like it can draw unicode.
+    pub fn parse(
+        args: impl IntoIterator<Item = String>,
+    ) -> Result<Self, String> {
+        let mut theme = Theme::Plain;
+        let mut plain = false;
+        let mut config = None;
+        let mut play = false;
+        let mut record = None;
+        let mut frame_delay = 500;
+        let mut stats = false;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--plain" => plain = true,
+                "--play" => play = true,
+                "--stats" => stats = true,
+                "--theme" => {
+                    let name = args.next().ok_or("`--theme` needs a value")?;
+                    theme = Theme::from_name(&name)
+                        .ok_or(format!("unknown theme `{name}`"))?;
+                },
+                "--config" => {
+                    let path = args.next().ok_or("`--config` needs a path")?;
+                    config = Some(path.into());
+                },
+                "--record" => {
+                    let path = args.next().ok_or("`--record` needs a path")?;
+                    record = Some(path.into());
+                },
+                "--frame-delay" => {
+                    let ms =
+                        args.next().ok_or("`--frame-delay` needs a value")?;
+                    frame_delay = ms
+                        .parse()
+                        .map_err(|_| format!("invalid frame delay `{ms}`"))?;
+                },
+                other => return Err(format!("unknown argument `{other}`")),
+            }
+        }
+
+        Ok(Self {
+            theme: if plain {
+                Theme::Plain
+            } else {
+                theme.or_fallback()
+            },
+            config,
+            play,
+            record,
+            frame_delay,
+            stats,
+        })
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+
+use lib_2048::MoveDirection;
+
+/// Something a key can be bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Move(MoveDirection),
+    /// Move in the board's next scripted direction
+    Next,
+    Restart,
+    Quit,
+    Undo,
+}
+
+impl Action {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "left" => Self::Move(MoveDirection::Left),
+            "up" => Self::Move(MoveDirection::Up),
+            "right" => Self::Move(MoveDirection::Right),
+            "down" => Self::Move(MoveDirection::Down),
+            "next" => Self::Next,
+            "restart" => Self::Restart,
+            "quit" => Self::Quit,
+            "undo" => Self::Undo,
+            _ => return None,
+        })
+    }
+}
+
+/// The keys used to play, by key name
+///
+/// Keys are single characters, or `up`, `down`, `left`, and `right` for the
+/// arrow keys.
+#[derive(Debug, Clone)]
+pub struct Bindings(HashMap<String, Action>);
+
+impl Default for Bindings {
+    /// Arrow keys and WASD to move, `n` for the next scripted move, `r` to
+    /// restart, `u` to undo, and `q` to quit
+    fn default() -> Self {
+        let keys = [
+            ("left", "left"),
+            ("up", "up"),
+            ("right", "right"),
+            ("down", "down"),
+            ("a", "left"),
+            ("w", "up"),
+            ("d", "right"),
+            ("s", "down"),
+            ("n", "next"),
+            ("r", "restart"),
+            ("u", "undo"),
+            ("q", "quit"),
+        ];
+
+        Self(
+            keys.iter()
+                .map(|(key, action)| {
+                    (key.to_string(), Action::from_name(action).unwrap())
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Bindings {
+    /// Loads bindings from the `[bindings]` table of a TOML config file, on
+    /// top of the [defaults](Self::default)
+    ///
+    /// ```toml
+    /// [bindings]
+    /// h = "left"
+    /// j = "down"
+    /// k = "up"
+    /// l = "right"
+    /// ```
+    ///
+    /// Each key can only be bound once in the file. Other tables are ignored.
+    /// Keys can be quoted, which is needed to bind `#` or `=`.
+    pub fn parse(config: &str) -> Result<Self, String> {
+        let mut bindings = Self::default();
+        let mut bound_on = HashMap::new();
+        let mut in_bindings = false;
+
+        for (i, line) in config.lines().enumerate() {
+            let line_no = i + 1;
+            let line = match find_unquoted(line, '#') {
+                Some(comment) => &line[..comment],
+                None => line,
+            }
+            .trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(table) =
+                line.strip_prefix('[').and_then(|l| l.strip_suffix(']'))
+            {
+                in_bindings = table.trim() == "bindings";
+                continue;
+            }
+
+            if !in_bindings {
+                continue;
+            }
+
+            let (key, action) = find_unquoted(line, '=')
+                .map(|eq| (&line[..eq], &line[eq + 1..]))
+                .ok_or(format!(
+                    "line {line_no}: expected `key = \"action\"`"
+                ))?;
+            let key = unquote(key.trim());
+            let action = unquote(action.trim());
+
+            let action = Action::from_name(action).ok_or(format!(
+                "line {line_no}: unknown action `{action}` for `{key}`"
+            ))?;
+
+            if let Some(first) = bound_on.insert(key.to_string(), line_no) {
+                return Err(format!(
+                    "line {line_no}: `{key}` is already bound on line {first}"
+                ));
+            }
+
+            bindings.0.insert(key.to_string(), action);
+        }
+
+        Ok(bindings)
+    }
+
+    /// Gets the action bound to `key`, if there is one
+    pub fn get(&self, key: &str) -> Option<Action> {
+        self.0.get(key).copied()
+    }
+}
+
+/// Finds the first `c` in `line` that isn't between double quotes, so keys
+/// like `"#"` can be bound
+fn find_unquoted(line: &str, c: char) -> Option<usize> {
+    let mut quoted = false;
+
+    for (i, ch) in line.char_indices() {
+        if ch == '"' {
+            quoted = !quoted;
+        } else if ch == c && !quoted {
+            return Some(i);
+        }
+    }
+
+    None
+}
+
+fn unquote(s: &str) -> &str {
+    s.strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .unwrap_or(s)
+}
+
+/// Splits a line typed at the terminal into key names, turning arrow key
+/// escape sequences into `up`, `down`, `left`, and `right`
+pub fn keys(line: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' && chars.peek() == Some(&'[') {
+            chars.next();
+
+            let arrow = match chars.next() {
+                Some('A') => "up",
+                Some('B') => "down",
+                Some('C') => "right",
+                Some('D') => "left",
+                _ => continue,
+            };
+            keys.push(arrow.to_string());
+        } else if !c.is_whitespace() {
+            keys.push(c.to_string());
+        }
+    }
+
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let bindings = Bindings::parse(
+            "[bindings]\nh = \"left\" # vi keys\nl = \"right\"\n",
+        )
+        .unwrap();
+        assert_eq!(bindings.get("h"), Some(Action::Move(MoveDirection::Left)));
+        assert_eq!(bindings.get("l"), Some(Action::Move(MoveDirection::Right)));
+        assert_eq!(bindings.get("q"), Some(Action::Quit));
+        assert_eq!(bindings.get("z"), None);
+    }
+
+    #[test]
+    fn parse_quoted_keys() {
+        let bindings = Bindings::parse(
+            "[bindings]\n\"#\" = \"quit\" # hash\n\"=\" = \"undo\"",
+        )
+        .unwrap();
+        assert_eq!(bindings.get("#"), Some(Action::Quit));
+        assert_eq!(bindings.get("="), Some(Action::Undo));
+    }
+
+    #[test]
+    fn parse_duplicate_key() {
+        let err =
+            Bindings::parse("[bindings]\nh = \"left\"\n\nh = \"right\"\n")
+                .unwrap_err();
+        assert_eq!(err, "line 4: `h` is already bound on line 2");
+    }
+
+    #[test]
+    fn parse_unknown_action() {
+        let err = Bindings::parse("[bindings]\nj = \"jump\"\n").unwrap_err();
+        assert_eq!(err, "line 2: unknown action `jump` for `j`");
+    }
+
+    #[test]
+    fn parse_other_tables() {
+        let bindings = Bindings::parse(
+            "[theme]\nh = \"jump\"\n[bindings]\nh = \"left\"\n[other]\nh = 1\n",
+        )
+        .unwrap();
+        assert_eq!(bindings.get("h"), Some(Action::Move(MoveDirection::Left)));
+    }
+
+    #[test]
+    fn keys() {
+        assert_eq!(
+            super::keys("\x1b[A\x1b[Dw q\x1b[C\x1b[B\x1b[Z"),
+            ["up", "left", "w", "q", "right", "down"]
+        );
+    }
+}
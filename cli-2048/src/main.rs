@@ -1,61 +1,203 @@
-use lib_2048::{BoardSpace::*, GameBoard, MoveDirection};
+mod args;
+mod bindings;
+mod record;
+mod theme;
+
+use std::{
+    env, fs,
+    io::{self, BufRead},
+    process,
+    sync::Arc,
+};
+
+use args::Args;
+use bindings::{Action, Bindings};
+use lib_2048::{format_score, prelude::*, timing::SystemClock, BoardSpace::*};
+use record::Recorder;
+use theme::Theme;
 
 fn main() {
+    let args = Args::parse(env::args().skip(1)).unwrap_or_else(|e| exit(e));
+    let mut screen = Screen {
+        theme: args.theme,
+        recorder: args.record.as_ref().map(|path| {
+            Recorder::create(
+                path,
+                GameBoard::empty().image_size(),
+                args.frame_delay,
+            )
+            .unwrap_or_else(|e| exit(e))
+        }),
+    };
+
+    let board = if args.play {
+        let bindings = match &args.config {
+            Some(path) => fs::read_to_string(path)
+                .map_err(|e| format!("couldn't read {}: {e}", path.display()))
+                .and_then(|config| Bindings::parse(&config)),
+            None => Ok(Bindings::default()),
+        }
+        .unwrap_or_else(|e| exit(e));
+
+        play(&bindings, &mut screen)
+    } else {
+        demo(&mut screen)
+    };
+
+    if args.stats {
+        print_report(&board);
+    }
+}
+
+fn exit(message: String) -> ! {
+    eprintln!("{message}");
+    process::exit(2);
+}
+
+/// Plays a game using keys typed at the terminal, one line at a time,
+/// returning the board as it was when the game ended or was quit
+fn play(bindings: &Bindings, screen: &mut Screen) -> GameBoard {
+    let mut board = GameBoard::new();
+    board.enable_timing(Arc::new(SystemClock::new()));
+    screen.draw(&board);
+
+    for line in io::stdin().lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => break,
+        };
+
+        // keys that aren't bound to anything are skipped
+        for key in bindings::keys(&line) {
+            match bindings.get(&key) {
+                Some(Action::Move(dir)) => {
+                    board.r#move(dir);
+                },
+                Some(Action::Next) => {
+                    let dir = board.next_scripted_direction();
+                    board.r#move(dir);
+                },
+                Some(Action::Restart) => {
+                    board.restart();
+                    println!(
+                        "best this session: {}",
+                        format_score(board.session_best(), Some(','))
+                    );
+                },
+                Some(Action::Undo) => {
+                    if let Err(e) = board.undo() {
+                        println!("{e}");
+                    }
+                },
+                Some(Action::Quit) => return board,
+                None => {},
+            }
+        }
+
+        print_score(&board);
+        screen.draw(&board);
+
+        if board.is_over() {
+            println!("game over! ({:?})", board.state());
+            break;
+        }
+    }
+
+    board
+}
+
+/// Plays a fixed sequence of moves, returning the final board
+fn demo(screen: &mut Screen) -> GameBoard {
     let mut board = GameBoard::from([
         [Tile(2), Tile(2), Tile(2), Tile(2)],
         [Tile(2), Tile(8), Tile(1), Tile(1)],
         [Vacant, Vacant, Vacant, Vacant],
         [Tile(2), Tile(4), Tile(1), Tile(2)],
     ]);
+    // script the spawns so the demo plays out the same every time
+    board.set_spawn_queue(vec![(None, 2); 9]);
 
-    print_grid(&board);
+    screen.draw(&board);
 
-    board.r#move(MoveDirection::Right);
-    println!("--- score: {} ---", &board.score);
-    print_grid(&board);
+    for dir in "rurlulluu".chars() {
+        board.apply_move_str(&dir.to_string()).unwrap();
+        print_score(&board);
+        screen.draw(&board);
+    }
 
-    board.r#move(MoveDirection::Up);
-    println!("--- score: {} ---", &board.score);
-    print_grid(&board);
+    board
+}
 
-    board.r#move(MoveDirection::Right);
-    println!("--- score: {} ---", &board.score);
-    print_grid(&board);
+/// Where boards are drawn: the terminal, and a GIF if one is being recorded
+struct Screen {
+    theme: Theme,
+    recorder: Option<Recorder>,
+}
 
-    board.r#move(MoveDirection::Left);
-    println!("--- score: {} ---", &board.score);
-    print_grid(&board);
+impl Screen {
+    fn draw(&mut self, board: &GameBoard) {
+        print_grid(board, self.theme);
 
-    board.r#move(MoveDirection::Up);
-    println!("--- score: {} ---", &board.score);
-    print_grid(&board);
+        if let Some(recorder) = &mut self.recorder {
+            if let Err(e) = recorder.frame(board) {
+                exit(format!("couldn't record frame: {e}"));
+            }
+        }
+    }
+}
 
-    board.r#move(MoveDirection::Left);
-    println!("--- score: {} ---", &board.score);
-    print_grid(&board);
+fn print_score(board: &GameBoard) {
+    println!("--- score: {} ---", format_score(board.score, Some(',')));
+}
 
-    board.r#move(MoveDirection::Left);
-    println!("--- score: {} ---", &board.score);
-    print_grid(&board);
+/// Prints a summary of a finished game, leaving out timing if it wasn't
+/// enabled
+fn print_report(board: &GameBoard) {
+    let merges: usize = board
+        .score_breakdown()
+        .iter()
+        .map(|(value, points)| points / value)
+        .sum();
 
-    board.r#move(MoveDirection::Up);
-    println!("--- score: {} ---", &board.score);
-    print_grid(&board);
+    println!("=== report ===");
+    println!("score: {}", format_score(board.score, Some(',')));
+    println!("moves: {}", board.moves());
+    println!("highest tile: {}", board.highest_tile().unwrap_or(0));
+    println!("merges: {merges}");
+    println!("biggest merge: {}", board.biggest_merge());
 
-    board.r#move(MoveDirection::Up);
-    println!("--- score: {} ---", &board.score);
-    print_grid(&board);
+    let mut tiles: Vec<(usize, usize)> = Vec::new();
+    for value in board.tiles_sorted_desc() {
+        match tiles.last_mut() {
+            Some((v, count)) if *v == value => *count += 1,
+            _ => tiles.push((value, 1)),
+        }
+    }
+    let tiles = tiles
+        .iter()
+        .map(|(value, count)| format!("{value} x{count}"))
+        .collect::<Vec<_>>();
+    println!("tiles: {}", tiles.join(", "));
+
+    if let Some(time) = board.total_play_time() {
+        println!("time: {:.1}s", time.as_secs_f64());
+        println!("moves per minute: {:.1}", board.moves_per_minute());
+    }
 }
 
-fn print_grid(board: &GameBoard) {
-    println!("   {:^9} {:^9} {:^9} {:^9}", "0", "1", "2", "3");
-    for (row, items) in board.cells.iter().enumerate() {
-        for (col, cell) in items.iter().enumerate() {
-            if col == 0 {
-                print!("{row} |{:^9}|", format!("{}", cell));
-            } else {
-                print!("{:^9}|", format!("{}", cell));
-            }
+fn print_grid(board: &GameBoard, theme: Theme) {
+    let width = theme.cell_width();
+
+    print!("  ");
+    for col in 0..board.size() {
+        print!(" {col:^width$}");
+    }
+    println!();
+
+    for (row, items) in board.rows().enumerate() {
+        print!("{row} |");
+        for cell in items {
+            print!("{}|", theme.cell(cell, width));
         }
 
         println!();
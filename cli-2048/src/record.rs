@@ -0,0 +1,109 @@
+use std::{
+    fs::File,
+    io::{Seek, SeekFrom, Write},
+    path::Path,
+};
+
+use gif::{Encoder, EncodingError, Frame, Repeat};
+use lib_2048::GameBoard;
+
+/// The byte that ends every GIF file
+const TRAILER: u8 = 0x3b;
+
+/// Writes boards to an animated GIF, one frame at a time
+///
+/// Each frame is encoded as soon as it's captured instead of being kept in
+/// memory, and the file always ends with a trailer, so quitting at any point
+/// leaves a valid GIF of every frame so far.
+pub struct Recorder {
+    encoder: Encoder<File>,
+    /// How long each frame is shown, in hundredths of a second
+    delay: u16,
+}
+
+impl Recorder {
+    /// Creates the GIF at `path` for boards drawn `size` pixels wide, showing
+    /// each frame for `delay_ms` milliseconds
+    ///
+    /// GIFs time frames in hundredths of a second, so the delay is rounded up
+    /// to the next one, and only a delay of `0` shows frames without waiting.
+    pub fn create(
+        path: &Path,
+        size: usize,
+        delay_ms: u16,
+    ) -> Result<Self, String> {
+        let error = |e: &dyn std::fmt::Display| {
+            format!("couldn't record to {}: {e}", path.display())
+        };
+
+        let size = u16::try_from(size).map_err(|e| error(&e))?;
+        let file = File::create(path).map_err(|e| error(&e))?;
+        let mut encoder =
+            Encoder::new(file, size, size, &[]).map_err(|e| error(&e))?;
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(|e| error(&e))?;
+
+        Ok(Self {
+            encoder,
+            delay: delay_ms.div_ceil(10),
+        })
+    }
+
+    /// Adds `board` to the end of the GIF
+    pub fn frame(&mut self, board: &GameBoard) -> Result<(), EncodingError> {
+        let size = board.image_size() as u16;
+        let mut pixels = board.to_rgba();
+
+        let mut frame = Frame::from_rgba_speed(size, size, &mut pixels, 10);
+        frame.delay = self.delay;
+        self.encoder.write_frame(&frame)?;
+
+        // the next frame writes over this trailer, and the encoder writes the
+        // real one when it's dropped
+        let file = self.encoder.get_mut();
+        file.write_all(&[TRAILER])?;
+        file.seek(SeekFrom::Current(-1))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, mem};
+
+    use gif::Decoder;
+    use lib_2048::MoveDirection;
+
+    use super::*;
+
+    #[test]
+    fn interrupted_recording() {
+        let path = std::env::temp_dir()
+            .join(format!("cli-2048-record-{}.gif", std::process::id()));
+        let mut board = GameBoard::with_seed(4);
+
+        let mut recorder =
+            Recorder::create(&path, board.image_size(), 15).unwrap();
+        for dir in
+            [MoveDirection::Left, MoveDirection::Up, MoveDirection::Right]
+        {
+            recorder.frame(&board).unwrap();
+            board.r#move(dir);
+        }
+
+        // quitting without dropping the recorder still leaves every frame
+        mem::forget(recorder);
+
+        let mut decoder = Decoder::new(File::open(&path).unwrap()).unwrap();
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 2);
+            frames += 1;
+        }
+        assert_eq!(frames, 3);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,86 @@
+use std::env;
+
+use lib_2048::BoardSpace;
+
+/// How tile values are drawn in the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    /// Plain ASCII digits
+    Plain,
+    /// Fullwidth unicode digits, for screenshots that look like the real game
+    Fancy,
+}
+
+impl Theme {
+    /// Looks up a theme by the name given to `--theme`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "plain" => Some(Self::Plain),
+            "fancy" => Some(Self::Fancy),
+            _ => None,
+        }
+    }
+
+    /// Falls back to the plain theme if the terminal doesn't look like it
+    /// can draw unicode
+    pub fn or_fallback(self) -> Self {
+        if supports_unicode() {
+            self
+        } else {
+            Self::Plain
+        }
+    }
+
+    /// Draws `cell` centered in a space `width` columns wide
+    pub fn cell(self, cell: &BoardSpace, width: usize) -> String {
+        let text = match (self, cell) {
+            (_, BoardSpace::Vacant) => String::new(),
+            (Self::Plain, BoardSpace::Tile(n)) => n.to_string(),
+            (Self::Fancy, BoardSpace::Tile(n)) => {
+                n.to_string().chars().map(fullwidth_digit).collect()
+            },
+        };
+
+        // fullwidth digits take up two columns each, so `{:^}` can't be used
+        // to center them
+        let columns = match self {
+            Self::Plain => text.chars().count(),
+            Self::Fancy => text.chars().count() * 2,
+        };
+        let padding = width.saturating_sub(columns);
+        let left = padding / 2;
+
+        format!("{}{text}{}", " ".repeat(left), " ".repeat(padding - left))
+    }
+
+    /// The number of columns each cell takes up
+    pub fn cell_width(self) -> usize {
+        match self {
+            Self::Plain => 9,
+            // wide enough for a fullwidth `131072`
+            Self::Fancy => 13,
+        }
+    }
+}
+
+fn fullwidth_digit(digit: char) -> char {
+    let offset = digit.to_digit(10).expect("tile values are only digits");
+    char::from_u32('０' as u32 + offset).unwrap()
+}
+
+/// Guesses whether the terminal can draw unicode from the locale environment
+/// variables
+fn supports_unicode() -> bool {
+    if env::var("TERM").is_ok_and(|term| term == "dumb") {
+        return false;
+    }
+
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .is_some_and(|value| {
+            let value = value.to_lowercase();
+            value.contains("utf-8") || value.contains("utf8")
+        })
+}
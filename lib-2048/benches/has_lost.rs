@@ -0,0 +1,29 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use lib_2048::{BoardSpace::*, GameBoard};
+
+fn has_lost(c: &mut Criterion) {
+    // the worst case: every space has to be checked to find no moves
+    let lost = GameBoard::from([
+        [Tile(2), Tile(4), Tile(2), Tile(4)],
+        [Tile(4), Tile(2), Tile(4), Tile(2)],
+        [Tile(2), Tile(4), Tile(2), Tile(4)],
+        [Tile(4), Tile(2), Tile(4), Tile(2)],
+    ]);
+
+    let playable = GameBoard::from([
+        [Tile(2), Tile(4), Tile(2), Tile(4)],
+        [Tile(4), Tile(2), Tile(4), Tile(2)],
+        [Tile(2), Tile(4), Tile(2), Tile(4)],
+        [Tile(4), Tile(2), Tile(4), Vacant],
+    ]);
+
+    c.bench_function("has_lost (lost)", |b| {
+        b.iter(|| black_box(&lost).has_lost())
+    });
+    c.bench_function("has_lost (playable)", |b| {
+        b.iter(|| black_box(&playable).has_lost())
+    });
+}
+
+criterion_group!(benches, has_lost);
+criterion_main!(benches);
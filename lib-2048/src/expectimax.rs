@@ -0,0 +1,258 @@
+//! Picking moves by searching ahead through every move and spawn
+//!
+//! The search alternates between the moves the player can make and the
+//! tiles that can spawn after them. Moves are scored by the best result they
+//! can lead to, and spawns by the average of their results, weighted by how
+//! likely each spawn is under the board's
+//! [`spawn_distribution`](GameBoard::spawn_distribution).
+//!
+//! [`GameBoard::ai_move`] wraps the search with a [`Difficulty`], for
+//! opponents that shouldn't always play their best.
+
+use std::collections::HashMap;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    slide_cells, BoardSpace, GameBoard, GameBoardCells, GameBoardLocation,
+    MoveDirection, GAME_BOARD_SIZE,
+};
+
+/// How much each space is worth holding a big tile in, snaking back and
+/// forth from the top left corner, so that tiles end up lined up to merge
+/// into each other
+const SNAKE: [[f64; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] = [
+    [15.0, 14.0, 13.0, 12.0],
+    [8.0, 9.0, 10.0, 11.0],
+    [7.0, 6.0, 5.0, 4.0],
+    [0.0, 1.0, 2.0, 3.0],
+];
+
+/// How well [`GameBoard::ai_move`] plays
+///
+/// Each difficulty makes random moves less often and searches deeper than
+/// the one before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Difficulty {
+    /// Makes a random move half of the time, and otherwise only looks at
+    /// the next move
+    Easy,
+    /// Makes a random move one time in ten, and otherwise looks two moves
+    /// ahead
+    Medium,
+    /// Never makes a random move, and looks three moves ahead
+    Hard,
+}
+
+impl Difficulty {
+    /// Gets the chance of making a random move, and how many moves ahead
+    /// to search otherwise
+    fn settings(self) -> (f64, usize) {
+        match self {
+            Self::Easy => (0.5, 1),
+            Self::Medium => (0.1, 2),
+            Self::Hard => (0.0, 3),
+        }
+    }
+}
+
+impl GameBoard {
+    /// Picks the move with the best expected result, looking `depth` moves
+    /// ahead, or `None` if no move changes the board
+    ///
+    /// After each move, every tile the board can spawn is tried in every
+    /// vacant space. Once there are more than `max_spawn_spaces` vacant
+    /// spaces, only that many of them are tried, spread out across the
+    /// board, so the search stays quick on emptier boards. Spawns are
+    /// treated as equally likely in every vacant space, whatever the
+    /// [`spawn_policy`](crate::GameConfig::spawn_policy).
+    ///
+    /// Searching deeper plays better, but each extra move multiplies the
+    /// time taken by around 8 times `max_spawn_spaces`.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection};
+    ///
+    /// let board = GameBoard::from([
+    ///     [Tile(64), Tile(64), Tile(2), Tile(4)],
+    ///     [Tile(4), Tile(8), Tile(4), Tile(2)],
+    ///     [Tile(2), Tile(4), Tile(2), Tile(4)],
+    ///     [Tile(4), Tile(2), Tile(4), Tile(2)],
+    /// ]);
+    ///
+    /// // merge the 64s into the top left corner, where big tiles are kept
+    /// assert_eq!(board.expectimax_move(2, 6), Some(MoveDirection::Left));
+    /// ```
+    pub fn expectimax_move(
+        &self,
+        depth: usize,
+        max_spawn_spaces: usize,
+    ) -> Option<MoveDirection> {
+        let mut search = Search {
+            board: self,
+            max_spawn_spaces: max_spawn_spaces.max(1),
+            memo: HashMap::new(),
+        };
+
+        search
+            .best_move(self.cells, depth.max(1))
+            .map(|(dir, _)| dir)
+    }
+
+    /// Picks a move playing at `difficulty`, or `None` if no move changes
+    /// the board
+    ///
+    /// Random moves are picked from the [legal moves](Self::legal_moves)
+    /// using `rng`, so the same seeded generator always plays the same
+    /// game. Searches try at most 4 spawn spaces, like
+    /// [`expectimax_move`](Self::expectimax_move) with a
+    /// `max_spawn_spaces` of 4.
+    ///
+    /// ```
+    /// use lib_2048::{Difficulty, GameBoard};
+    /// use rand::{rngs::StdRng, SeedableRng};
+    ///
+    /// let board = GameBoard::with_seed(3);
+    /// let mut rng = StdRng::seed_from_u64(7);
+    ///
+    /// assert_eq!(
+    ///     board.ai_move(Difficulty::Hard, &mut rng),
+    ///     board.expectimax_move(3, 4)
+    /// );
+    /// ```
+    pub fn ai_move(
+        &self,
+        difficulty: Difficulty,
+        rng: &mut impl Rng,
+    ) -> Option<MoveDirection> {
+        let (random_chance, depth) = difficulty.settings();
+        if rng.gen_bool(random_chance) {
+            return self.legal_moves().choose(rng).copied();
+        }
+
+        self.expectimax_move(depth, 4)
+    }
+}
+
+struct Search<'a> {
+    board: &'a GameBoard,
+    max_spawn_spaces: usize,
+    /// The value of the best move from each board already searched, by how
+    /// many moves were left to search from it
+    memo: HashMap<(GameBoardCells, usize), f64>,
+}
+
+impl Search<'_> {
+    /// Gets the move from `cells` with the highest expected value, and that
+    /// value
+    fn best_move(
+        &mut self,
+        cells: GameBoardCells,
+        depth: usize,
+    ) -> Option<(MoveDirection, f64)> {
+        let mut best: Option<(MoveDirection, f64)> = None;
+
+        for dir in MoveDirection::all() {
+            if !self.board.config.allows(dir) {
+                continue;
+            }
+
+            let mut after = cells;
+            let outcome = slide_cells(&mut after, dir, &self.board.config);
+            if !outcome.moved {
+                continue;
+            }
+
+            let value = outcome.score_delta as f64 + self.spawns(after, depth);
+            if best.is_none_or(|(_, v)| value > v) {
+                best = Some((dir, value));
+            }
+        }
+
+        best
+    }
+
+    /// Gets the value of the board after a move, averaged over the tiles
+    /// that can spawn next
+    fn spawns(&mut self, cells: GameBoardCells, depth: usize) -> f64 {
+        let spaces = self.spawn_spaces(&cells);
+        if spaces.is_empty() {
+            return self.moves(cells, depth);
+        }
+
+        let mut total = 0.0;
+        for (x, y) in &spaces {
+            for (value, chance) in &self.board.config.spawn_distribution {
+                let mut child = cells;
+                child[*y][*x] = BoardSpace::Tile(*value);
+                total += chance * self.moves(child, depth);
+            }
+        }
+
+        total / spaces.len() as f64
+    }
+
+    /// Gets the value of the board before a move, with `depth` moves,
+    /// counting the one just made, left to search
+    fn moves(&mut self, cells: GameBoardCells, depth: usize) -> f64 {
+        if depth <= 1 {
+            return evaluate(&cells);
+        }
+
+        if let Some(value) = self.memo.get(&(cells, depth)) {
+            return *value;
+        }
+
+        // a board with no moves left has lost, which is worse than anything
+        // else
+        let value = self
+            .best_move(cells, depth - 1)
+            .map_or(0.0, |(_, value)| value);
+        self.memo.insert((cells, depth), value);
+
+        value
+    }
+
+    /// Gets the vacant spaces to try spawning into, at most
+    /// `max_spawn_spaces` evenly spread ones
+    fn spawn_spaces(&self, cells: &GameBoardCells) -> Vec<GameBoardLocation> {
+        let vacant = cells
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, space)| **space == BoardSpace::Vacant)
+                    .map(move |(x, _)| (x, y))
+            })
+            .collect::<Vec<_>>();
+
+        if vacant.len() <= self.max_spawn_spaces {
+            return vacant;
+        }
+
+        (0..self.max_spawn_spaces)
+            .map(|i| vacant[i * vacant.len() / self.max_spawn_spaces])
+            .collect()
+    }
+}
+
+/// Scores how good a board is to play on, higher being better
+///
+/// Vacant spaces leave room to keep playing, and big tiles lined up along
+/// the [`SNAKE`] can be merged into each other one after another.
+fn evaluate(cells: &GameBoardCells) -> f64 {
+    let mut vacant = 0.0;
+    let mut snake = 0.0;
+
+    for (y, row) in cells.iter().enumerate() {
+        for (x, space) in row.iter().enumerate() {
+            match space {
+                BoardSpace::Vacant => vacant += 1.0,
+                BoardSpace::Tile(t) => snake += *t as f64 * SNAKE[y][x],
+            }
+        }
+    }
+
+    vacant * 64.0 + snake / 8.0
+}
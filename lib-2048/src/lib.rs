@@ -1,375 +1,6473 @@
-use std::fmt;
+mod expectimax;
+pub mod prelude;
+#[cfg(feature = "render")]
+pub mod render;
+mod rng;
+#[cfg(feature = "serde")]
+mod ser;
+mod share;
+pub mod timing;
+mod trajectory;
 
-#[cfg(not(test))]
-use rand::{rngs::OsRng, seq::SliceRandom, Rng};
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    error::Error,
+    fmt,
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
+
+use rand::{seq::SliceRandom, Rng};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub use crate::expectimax::Difficulty;
+pub use crate::rng::RngState;
+#[cfg(feature = "serde")]
+pub use crate::ser::JsonError;
+pub use crate::share::ShareCodeError;
+pub use crate::trajectory::{Step, Trajectory};
+use crate::{
+    rng::SpawnRng,
+    timing::{Clock, MoveTimer},
+};
 
 /// Default dimensions of the [`GameBoard`]
 pub const GAME_BOARD_SIZE: usize = 4;
 pub const STARTING_TILES: usize = 2;
+/// The default value of the tile that wins the game
+pub const WIN_TARGET: usize = 2048;
 
 /// A representation of the location of a space on the game board
 pub type GameBoardLocation = (usize, usize);
 
+/// The grid of spaces making up a [`GameBoard`], indexed as `[y][x]`
+pub type GameBoardCells = [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE];
+
+/// Tunable rules used when creating and playing a [`GameBoard`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct GameConfig {
+    /// The number of tiles spawned on a freshly created board
+    pub starting_tiles: usize,
+    /// The value of each tile that can spawn, with the chance it's picked
+    ///
+    /// The chances are scaled to add up to `1.0` when a board is created.
+    pub spawn_distribution: Vec<(usize, f64)>,
+    /// The tile value that must be reached to win the game
+    pub win_target: usize,
+    /// What happens once the [`win_target`](Self::win_target) is reached
+    pub win_behavior: WinBehavior,
+    /// The number of tiles spawned after each move that changes the board
+    ///
+    /// Each tile spawns into a different vacant space, so fewer tiles are
+    /// spawned if the board fills up
+    pub spawn_per_move: usize,
+    /// The seed used to pick spawned tiles, making games reproducible
+    ///
+    /// With no seed, spawns are picked using the operating system's random
+    /// number generator.
+    pub seed: Option<u64>,
+    /// Whether tiles wrap around the edges of the board, so the tiles at
+    /// either end of a row or column can merge with each other
+    pub wrap: bool,
+    /// The smallest merged tile value that earns a bonus tile
+    ///
+    /// Each merge making a tile of at least this value spawns one extra tile
+    /// at the end of the move, on top of
+    /// [`spawn_per_move`](Self::spawn_per_move).
+    pub bonus_threshold: Option<usize>,
+    /// How the space a new tile spawns in is picked
+    pub spawn_policy: SpawnPolicy,
+    /// The number of moves after which the game ends
+    pub move_limit: Option<usize>,
+    /// Which directions can be moved in, indexed by [`MoveDirection`]
+    ///
+    /// Moving in any other direction does nothing, and a game that can only
+    /// be continued by moving in one of them is lost.
+    pub allowed_directions: [bool; 4],
+    /// The directions [`next_scripted_direction`] cycles through, for playing
+    /// with a single input
+    ///
+    /// An empty cycle goes through every direction in the order of
+    /// [`MoveDirection::all`].
+    ///
+    /// [`next_scripted_direction`]: GameBoard::next_scripted_direction
+    pub direction_cycle: Vec<MoveDirection>,
+    /// The number of moves that can be [undone](GameBoard::undo) in a game,
+    /// or `None` for no limit
+    pub undos: Option<usize>,
+    /// The smallest tile value that can merge
+    ///
+    /// Smaller tiles still slide, but two of them meeting stay side by side.
+    pub min_merge_value: usize,
+    /// The points scored for every move that changes the board, on top of
+    /// any merges
+    pub per_move_score: usize,
+    /// The points scored for each tile spawned after a move
+    ///
+    /// The starting tiles aren't scored.
+    pub spawn_score: SpawnScore,
+    /// Makes tiles that go too many moves without merging lose value, or
+    /// `None` to keep every tile until it merges
+    pub decay: Option<Decay>,
+    /// Rewards merging moves made soon after each other, or `None` to treat
+    /// every move the same
+    pub combo: Option<Combo>,
+    /// Whether [`merge_until_stable`](GameBoard::merge_until_stable) keeps
+    /// sliding until merged tiles stop merging again, instead of stopping
+    /// after a single slide like a normal move
+    pub cascade_merges: bool,
+}
+
+impl GameConfig {
+    /// Checks if moving in `dir` is allowed by
+    /// [`allowed_directions`](Self::allowed_directions)
+    pub fn allows(&self, dir: MoveDirection) -> bool {
+        self.allowed_directions[dir as usize]
+    }
+
+    /// Checks if tiles worth `a` and `b` merge when they meet
+    ///
+    /// ```
+    /// use lib_2048::GameConfig;
+    ///
+    /// let config = GameConfig {
+    ///     min_merge_value: 8,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert!(config.can_merge(8, 8));
+    /// assert!(!config.can_merge(4, 4));
+    /// assert!(!config.can_merge(8, 16));
+    /// ```
+    pub fn can_merge(&self, a: usize, b: usize) -> bool {
+        a == b && a >= self.min_merge_value
+    }
+}
+
+/// Which vacant spaces new tiles are allowed to spawn in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SpawnPolicy {
+    /// Any vacant space, all equally likely
+    #[default]
+    Uniform,
+    /// Vacant spaces that aren't next to the highest tile, or any vacant
+    /// space if they all are
+    AvoidHighestTile,
+    /// Vacant spaces where the new tile doesn't end the game, or any vacant
+    /// space if it would end the game anywhere
+    ///
+    /// A board with a vacant space left always has a move with every
+    /// direction allowed, so this only matters when some
+    /// [`allowed_directions`](GameConfig::allowed_directions) are turned off.
+    KeepAlive,
+}
+
+/// How many points a spawned tile is worth, for
+/// [`GameConfig::spawn_score`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SpawnScore {
+    /// Spawned tiles aren't worth anything, like in the original game
+    #[default]
+    Nothing,
+    /// Every spawned tile is worth the same number of points
+    Flat(usize),
+    /// Spawned tiles are worth their value
+    TileValue,
+}
+
+impl SpawnScore {
+    /// Gets the points scored for spawning a tile worth `value`
+    ///
+    /// ```
+    /// use lib_2048::SpawnScore;
+    ///
+    /// assert_eq!(SpawnScore::Nothing.points(4), 0);
+    /// assert_eq!(SpawnScore::Flat(1).points(4), 1);
+    /// assert_eq!(SpawnScore::TileValue.points(4), 4);
+    /// ```
+    pub fn points(self, value: usize) -> usize {
+        match self {
+            Self::Nothing => 0,
+            Self::Flat(points) => points,
+            Self::TileValue => value,
+        }
+    }
+}
+
+/// A rule making tiles that sit for too long without merging decay, for
+/// [`GameConfig::decay`]
+///
+/// At the end of each move, before new tiles spawn, every tile that's gone
+/// [`after_moves`](Self::after_moves) moves without merging is halved, and
+/// counts its moves again from 0. Merging or spawning also starts a tile's
+/// count again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Decay {
+    /// The number of moves in a row a tile can go without merging before it
+    /// decays
+    pub after_moves: usize,
+    /// What happens to a tile that would be halved below the smallest value
+    /// in the [`spawn_distribution`](GameConfig::spawn_distribution)
+    pub floor: DecayFloor,
+}
+
+/// What happens to a tile that decays below the smallest tile that can
+/// spawn, for [`Decay::floor`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DecayFloor {
+    /// The tile is removed, leaving its space vacant
+    #[default]
+    Vanish,
+    /// The tile becomes the smallest tile that can spawn instead
+    Keep,
+}
+
+/// A rule rewarding merges that follow each other quickly, for
+/// [`GameConfig::combo`]
+///
+/// Any move made within the [`window`](Self::window) of the last move that
+/// merged tiles is a combo, whether or not it merges anything itself: only
+/// [bonus tiles](GameConfig::bonus_threshold) spawn after it, and its merges
+/// are worth [`multiplier`](Self::multiplier) times their usual points.
+/// Every move that merges tiles, combo or not, opens the window again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Combo {
+    pub window: ComboWindow,
+    /// How many times their usual points a combo's merges are worth
+    pub multiplier: usize,
+}
+
+/// How soon a move has to follow the last one that merged tiles to be a
+/// combo, for [`Combo::window`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ComboWindow {
+    /// Within this many moves, counting those that don't merge, so `1` means
+    /// only the very next move
+    Moves(usize),
+    /// Within this long, timed by the clock given to
+    /// [`enable_timing`](GameBoard::enable_timing)
+    ///
+    /// No move is a combo while timing is off.
+    Time(Duration),
+}
+
+/// What happens when a [`GameBoard`] reaches its
+/// [`win_target`](GameConfig::win_target)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WinBehavior {
+    /// The game is over and no more moves can be made
+    Stop,
+    /// Play goes on, but the winning move is marked with
+    /// [`just_won`](MoveOutcome::just_won) so it can be announced
+    Prompt,
+    /// Play goes on as if nothing happened
+    #[default]
+    Continue,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            starting_tiles: STARTING_TILES,
+            spawn_distribution: vec![(2, 0.9), (4, 0.1)],
+            win_target: WIN_TARGET,
+            win_behavior: WinBehavior::Continue,
+            spawn_per_move: 1,
+            seed: None,
+            wrap: false,
+            bonus_threshold: None,
+            spawn_policy: SpawnPolicy::Uniform,
+            move_limit: None,
+            allowed_directions: [true; 4],
+            direction_cycle: MoveDirection::all().to_vec(),
+            undos: None,
+            min_merge_value: 0,
+            per_move_score: 0,
+            spawn_score: SpawnScore::Nothing,
+            decay: None,
+            combo: None,
+            cascade_merges: false,
+        }
+    }
+}
+
+/// Whether a [`GameBoard`] is still being played, from
+/// [`GameBoard::state`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameState {
+    /// At least one move can still change the board
+    Playing,
+    /// The [win target](GameConfig::win_target) was reached, and the
+    /// [`win_behavior`](GameConfig::win_behavior) is [`WinBehavior::Stop`]
+    Won,
+    /// No move can change the board, or the game was
+    /// [ended early](GameBoard::end_game)
+    Lost,
+    /// The [move limit](GameConfig::move_limit) was used up
+    LimitReached,
+}
+
 /// Represents the grid of tiles making up the game
 ///
 /// The `(0,0)` origin of the board is located in the top-left corner of the
 /// board, with coordinates increasing as you move toward the bottom-right of
 /// the board. Coordinates are in the form (row, column)
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "ser::SavedGame", into = "ser::SavedGame")
+)]
 pub struct GameBoard {
+    /// The spaces making up the board, indexed as `cells[y][x]`
+    ///
+    /// Prefer [`get`](Self::get), [`set`](Self::set), [`rows`](Self::rows),
+    /// and [`spaces`](Self::spaces), which will keep working if the board's
+    /// storage changes.
     pub cells: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE],
     pub score: usize,
-    #[cfg(not(test))]
-    rng: OsRng,
+    score_breakdown: BTreeMap<usize, usize>,
+    biggest_merge: usize,
+    moves: usize,
+    version: u64,
+    ended: bool,
+    ever_won: bool,
+    session_best: usize,
+    config: GameConfig,
+    timer: Option<MoveTimer>,
+    rng: SpawnRng,
+    spawn_queue: VecDeque<(Option<GameBoardLocation>, usize)>,
+    last_spawns: Vec<GameBoardLocation>,
+    cycle_pos: usize,
+    history: Vec<Turn>,
+    undos_remaining: Option<usize>,
+    staged: Option<Box<StagedMove>>,
+    max_tile_positions: Vec<GameBoardLocation>,
+    /// The number of moves each tile has gone without merging, indexed like
+    /// `cells`, only counted while tiles [decay](GameConfig::decay)
+    ages: [[usize; GAME_BOARD_SIZE]; GAME_BOARD_SIZE],
+    last_merge: Option<LastMerge>,
+}
+
+/// When the last move that merged tiles was made, for opening a
+/// [combo](GameConfig::combo) window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LastMerge {
+    /// The [move count](GameBoard::moves) just after the move
+    moves: usize,
+    /// The time on the board's clock, if timing was on
+    at: Option<Duration>,
+}
+
+/// A move [staged](GameBoard::stage_move) to be made later
+#[derive(Debug, Clone)]
+struct StagedMove {
+    board: GameBoard,
+    outcome: MoveOutcome,
+}
+
+/// What a [`GameBoard`] looked like before a move, so it can be undone
+#[derive(Debug, Clone)]
+struct Turn {
+    cells: GameBoardCells,
+    score: usize,
+    score_breakdown: BTreeMap<usize, usize>,
+    biggest_merge: usize,
+    moves: usize,
+    ever_won: bool,
+    rng: SpawnRng,
+    ages: [[usize; GAME_BOARD_SIZE]; GAME_BOARD_SIZE],
+    last_merge: Option<LastMerge>,
 }
 
 impl GameBoard {
     /// Creates a new square [`GameBoard`](Self) with dimensions [`GAME_BOARD_SIZE`], prefilled
     /// with two tiles
     pub fn new() -> Self {
-        let mut board = Self::empty();
+        Self::with_cells_and_config(GameConfig::default(), None, 0)
+    }
+
+    /// Creates a new [`GameBoard`](Self) like [`new`](Self::new), but with
+    /// every spawn picked using `seed`
+    ///
+    /// Boards created with the same seed and played with the same moves are
+    /// identical.
+    ///
+    /// ```
+    /// use lib_2048::GameBoard;
+    ///
+    /// assert_eq!(GameBoard::with_seed(7).cells, GameBoard::with_seed(7).cells);
+    /// ```
+    pub fn with_seed(seed: u64) -> Self {
+        let config = GameConfig {
+            seed: Some(seed),
+            ..Default::default()
+        };
+
+        Self::with_cells_and_config(config, None, 0)
+    }
+
+    /// Creates a seeded [`GameBoard`](Self) with `fill_ratio` of its spaces
+    /// already filled, for puzzles that start further into a game
+    ///
+    /// Tiles are powers of two from 2 to 128, with each value half as likely
+    /// as the one below it. At least one tile is always placed, and boards
+    /// with no moves are thrown out and generated again, so the same `seed`
+    /// and `fill_ratio` always give the same playable board. Later spawns
+    /// continue from `seed` like [`with_seed`](Self::with_seed).
+    ///
+    /// ```
+    /// use lib_2048::GameBoard;
+    ///
+    /// let board = GameBoard::puzzle_from_seed(7, 0.75);
+    ///
+    /// assert_eq!(board.fullness(), 0.75);
+    /// assert_eq!(board.cells, GameBoard::puzzle_from_seed(7, 0.75).cells);
+    /// assert!(!board.is_over());
+    /// ```
+    pub fn puzzle_from_seed(seed: u64, fill_ratio: f64) -> Self {
+        let config = GameConfig {
+            seed: Some(seed),
+            ..Default::default()
+        };
+        let spaces = (GAME_BOARD_SIZE * GAME_BOARD_SIZE) as f64;
+        let filled =
+            ((fill_ratio.clamp(0.0, 1.0) * spaces).round() as usize).max(1);
+
+        let mut board = Self::with_cells_and_config(
+            config,
+            Some([[BoardSpace::Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE]),
+            0,
+        );
+
+        loop {
+            board.cells =
+                [[BoardSpace::Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE];
+
+            let mut locations: Vec<_> = (0..GAME_BOARD_SIZE)
+                .flat_map(|y| (0..GAME_BOARD_SIZE).map(move |x| (x, y)))
+                .collect();
+            locations.shuffle(&mut board.rng);
+
+            for &loc in &locations[..filled] {
+                let mut value = 2;
+                while value < 128 && board.rng.gen_bool(0.5) {
+                    value *= 2;
+                }
+
+                board.set(loc, BoardSpace::Tile(value));
+            }
+
+            if board.can_move() {
+                return board;
+            }
+        }
+    }
+
+    /// Creates a [`GameBoard`](Self) with only the given tiles on it, to
+    /// give a player a head start
+    ///
+    /// No other tiles are spawned until the first move, and the score starts
+    /// at 0, so set [`score`](Self::score) afterwards if the handicap should
+    /// count toward it.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, HandicapError};
+    ///
+    /// let board = GameBoard::with_handicap(&[((0, 0), 64), ((3, 3), 2)])?;
+    /// assert_eq!(board.get((0, 0)), Tile(64));
+    /// assert_eq!(board.empty_count(), 14);
+    ///
+    /// assert_eq!(
+    ///     GameBoard::with_handicap(&[((0, 0), 64), ((0, 0), 2)]).unwrap_err(),
+    ///     HandicapError::Overlapping { index: 1 },
+    /// );
+    /// # Ok::<(), HandicapError>(())
+    /// ```
+    pub fn with_handicap(
+        tiles: &[(GameBoardLocation, usize)],
+    ) -> Result<Self, HandicapError> {
+        let mut cells =
+            [[BoardSpace::Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE];
+
+        for (index, &((x, y), value)) in tiles.iter().enumerate() {
+            if x >= GAME_BOARD_SIZE || y >= GAME_BOARD_SIZE {
+                return Err(HandicapError::OutOfBounds { index });
+            }
+            if value == 0 {
+                return Err(HandicapError::InvalidTile { index });
+            }
+            if cells[y][x] != BoardSpace::Vacant {
+                return Err(HandicapError::Overlapping { index });
+            }
+
+            cells[y][x] = BoardSpace::Tile(value);
+        }
+
+        let board =
+            Self::with_cells_and_config(GameConfig::default(), Some(cells), 0);
+        if !board.can_move() {
+            return Err(HandicapError::NotPlayable);
+        }
+
+        Ok(board)
+    }
+
+    /// Creates a new [`GameBoard`](Self) played with the rules in `config`
+    ///
+    /// If `cells` is given, the board starts with exactly those spaces and
+    /// `score`. Otherwise, the board starts empty and
+    /// [`starting_tiles`](GameConfig::starting_tiles) tiles are spawned on it.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, GameConfig};
+    ///
+    /// let cells = [
+    ///     [Tile(2), Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Tile(4)],
+    /// ];
+    ///
+    /// let board =
+    ///     GameBoard::with_cells_and_config(GameConfig::default(), Some(cells), 8);
+    ///
+    /// assert_eq!(board.cells, cells);
+    /// assert_eq!(board.score, 8);
+    /// ```
+    pub fn with_cells_and_config(
+        config: GameConfig,
+        cells: Option<GameBoardCells>,
+        score: usize,
+    ) -> Self {
+        let rng = SpawnRng::new(config.seed);
+        Self::with_parts(config, cells, score, rng)
+    }
+
+    /// Creates a new [`GameBoard`](Self) like [`new`](Self::new), but with
+    /// every spawn picked using `rng`
+    ///
+    /// The generator is cloned along with the board, including for each
+    /// move kept to [undo](Self::undo), so undoing a move also rewinds it.
+    ///
+    /// ```
+    /// use lib_2048::GameBoard;
+    /// use rand::{rngs::StdRng, SeedableRng};
+    ///
+    /// let a = GameBoard::new_with_rng(StdRng::seed_from_u64(7));
+    /// let b = GameBoard::new_with_rng(StdRng::seed_from_u64(7));
+    ///
+    /// assert_eq!(a.cells, b.cells);
+    /// assert_eq!(a.rng_state(), None);
+    /// ```
+    pub fn new_with_rng<R>(rng: R) -> Self
+    where
+        R: Rng + Clone + Send + Sync + 'static,
+    {
+        Self::with_parts(
+            GameConfig::default(),
+            None,
+            0,
+            SpawnRng::Custom(Box::new(rng)),
+        )
+    }
+
+    fn with_parts(
+        mut config: GameConfig,
+        cells: Option<GameBoardCells>,
+        score: usize,
+        rng: SpawnRng,
+    ) -> Self {
+        config.spawn_distribution =
+            normalize_distribution(config.spawn_distribution);
+
+        let mut board = Self {
+            cells: cells.unwrap_or(
+                [[BoardSpace::Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE],
+            ),
+            score,
+            score_breakdown: BTreeMap::new(),
+            biggest_merge: 0,
+            moves: 0,
+            version: 0,
+            ended: false,
+            ever_won: false,
+            session_best: 0,
+            timer: None,
+            rng,
+            spawn_queue: VecDeque::new(),
+            last_spawns: Vec::new(),
+            cycle_pos: 0,
+            history: Vec::new(),
+            max_tile_positions: Vec::new(),
+            undos_remaining: config.undos,
+            staged: None,
+            ages: [[0; GAME_BOARD_SIZE]; GAME_BOARD_SIZE],
+            last_merge: None,
+            config,
+        };
+
+        if cells.is_none() {
+            board.add_random_tiles(board.config.starting_tiles);
+        }
+        board.ever_won = board.has_winning_tile();
+
+        board
+    }
+
+    /// Creates a new blank square [`GameBoard`](Self) with dimensions
+    /// [`GAME_BOARD_SIZE`]
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard};
+    ///
+    /// assert_eq!(
+    ///     GameBoard::empty().cells,
+    ///     [
+    ///         [Vacant, Vacant, Vacant, Vacant],
+    ///         [Vacant, Vacant, Vacant, Vacant],
+    ///         [Vacant, Vacant, Vacant, Vacant],
+    ///         [Vacant, Vacant, Vacant, Vacant],
+    ///     ]
+    /// );
+    /// ```
+    pub fn empty() -> Self {
+        Self::with_cells_and_config(
+            GameConfig::default(),
+            Some([[BoardSpace::Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE]),
+            0,
+        )
+    }
+
+    /// Gets the spaces making up the board
+    pub fn cells(&self) -> &GameBoardCells {
+        &self.cells
+    }
+
+    /// Gets a copy of the spaces making up the board
+    pub fn cells_owned(&self) -> GameBoardCells {
+        self.cells
+    }
+
+    /// Gets the number of spaces along each side of the board
+    pub fn size(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Gets the spaces in row `y`, from left to right
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is off the board.
+    pub fn row(&self, y: usize) -> &[BoardSpace] {
+        &self.cells[y]
+    }
+
+    /// Iterates over the rows of the board, from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[BoardSpace]> {
+        self.cells.iter().map(|row| &row[..])
+    }
+
+    /// Iterates over every space on the board with its location, row by row
+    /// from the top left
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard};
+    ///
+    /// let mut board = GameBoard::empty();
+    /// board.set((1, 2), Tile(4));
+    ///
+    /// let tiles: Vec<_> =
+    ///     board.spaces().filter(|(_, space)| *space != Vacant).collect();
+    /// assert_eq!(tiles, [((1, 2), Tile(4))]);
+    /// ```
+    pub fn spaces(
+        &self,
+    ) -> impl Iterator<Item = (GameBoardLocation, BoardSpace)> + '_ {
+        self.rows().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, space)| ((x, y), *space))
+        })
+    }
+
+    /// Gets the state of the random number generator used to spawn tiles, or
+    /// `None` if the board isn't [seeded](GameConfig::seed)
+    pub fn rng_state(&self) -> Option<RngState> {
+        self.rng.state()
+    }
+
+    /// Restores the random number generator from a previous
+    /// [`rng_state`](Self::rng_state), so that future spawns continue the same
+    /// sequence as the board it was taken from
+    ///
+    /// ```
+    /// use lib_2048::{GameBoard, MoveDirection};
+    ///
+    /// let mut original = GameBoard::with_seed(42);
+    /// original.r#move(MoveDirection::Left);
+    ///
+    /// // save the game...
+    /// let cells = original.cells_owned();
+    /// let state = original.rng_state().unwrap();
+    ///
+    /// // ...and load it again
+    /// let mut loaded = GameBoard::with_cells_and_config(
+    ///     original.config().clone(),
+    ///     Some(cells),
+    ///     original.score,
+    /// );
+    /// loaded.restore_rng_state(state);
+    ///
+    /// for dir in MoveDirection::all() {
+    ///     original.r#move(dir);
+    ///     loaded.r#move(dir);
+    /// }
+    ///
+    /// assert_eq!(original.cells, loaded.cells);
+    /// ```
+    pub fn restore_rng_state(&mut self, state: RngState) {
+        self.config.seed = Some(state.seed);
+        self.rng = SpawnRng::from_state(state);
+    }
+
+    /// Scripts the next spawns on this board, replacing any that are still
+    /// queued
+    ///
+    /// Each spawn is a location and the value of the tile to place there.
+    /// With no location, or one that's already taken, the tile goes in the
+    /// first vacant space in [`all_empty_spaces`](Self::all_empty_spaces)
+    /// order. Once the queue runs out, tiles spawn randomly again.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection};
+    ///
+    /// let mut board = GameBoard::from([
+    ///     [Tile(2), Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ]);
+    /// board.set_spawn_queue(vec![(Some((3, 3)), 4), (None, 8)]);
+    ///
+    /// board.r#move(MoveDirection::Right);
+    /// assert_eq!(board.get((3, 3)), Tile(4));
+    ///
+    /// board.r#move(MoveDirection::Left);
+    /// assert_eq!(board.get((1, 0)), Tile(8));
+    ///
+    /// // the queue is used up, so this spawn is random
+    /// board.r#move(MoveDirection::Down);
+    /// assert_eq!(board.empty_count(), 12);
+    /// ```
+    pub fn set_spawn_queue(
+        &mut self,
+        queue: Vec<(Option<GameBoardLocation>, usize)>,
+    ) {
+        self.spawn_queue = queue.into();
+    }
+
+    /// Gets the rules this board is played with
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    /// Gets the value of each tile that can spawn, with the chance it's
+    /// picked
+    ///
+    /// The chances always add up to `1.0`.
+    ///
+    /// ```
+    /// use lib_2048::GameBoard;
+    ///
+    /// let mut board = GameBoard::new();
+    /// assert_eq!(board.spawn_distribution(), [(2, 0.9), (4, 0.1)]);
+    ///
+    /// board.set_spawn_distribution(vec![(2, 1.0), (8, 3.0)]);
+    /// assert_eq!(board.spawn_distribution(), [(2, 0.25), (8, 0.75)]);
+    /// ```
+    pub fn spawn_distribution(&self) -> &[(usize, f64)] {
+        &self.config.spawn_distribution
+    }
+
+    /// Changes which tiles can spawn from now on, and how likely each one is
+    ///
+    /// The chances don't have to add up to `1.0`; they're scaled so they do.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `distribution` is empty, or has a negative chance or no
+    /// chances above zero.
+    pub fn set_spawn_distribution(&mut self, distribution: Vec<(usize, f64)>) {
+        self.config.spawn_distribution = normalize_distribution(distribution);
+    }
+
+    /// Starts timing every move made from now on using `clock`
+    ///
+    /// Timing is off by default. Enabling it again restarts the timer.
+    pub fn enable_timing(&mut self, clock: Arc<dyn Clock>) {
+        self.timer = Some(MoveTimer::new(clock));
+    }
+
+    /// Gets the time between timing being enabled and the most recent move,
+    /// or `None` if timing is disabled
+    pub fn total_play_time(&self) -> Option<Duration> {
+        self.timer.as_ref().map(MoveTimer::total_play_time)
+    }
+
+    /// Gets the shortest time taken to make a move, or `None` if timing is
+    /// disabled or no moves have been made
+    pub fn fastest_move(&self) -> Option<Duration> {
+        self.timer.as_ref().and_then(MoveTimer::fastest_move)
+    }
+
+    /// Gets the average time taken to make a move, or `None` if timing is
+    /// disabled or no moves have been made
+    pub fn average_move_time(&self) -> Option<Duration> {
+        self.timer.as_ref().and_then(MoveTimer::average_move_time)
+    }
+
+    /// Gets the number of moves made per minute since timing was enabled, or
+    /// `0.0` if timing is disabled or no time has passed
+    pub fn moves_per_minute(&self) -> f64 {
+        self.timer.as_ref().map_or(0.0, MoveTimer::moves_per_minute)
+    }
+
+    /// Gets the average points scored per move, or `0.0` if no moves have
+    /// been made
+    pub fn score_per_move(&self) -> f64 {
+        if self.moves == 0 {
+            return 0.0;
+        }
+
+        self.score as f64 / self.moves as f64
+    }
+
+    /// Gets the value of a cell on the game board
+    pub fn get(&self, (x, y): GameBoardLocation) -> BoardSpace {
+        self.cells[y][x]
+    }
+
+    /// Sets the value of a cell on the game board
+    ///
+    /// A tile set this way counts as new when tiles
+    /// [decay](GameConfig::decay).
+    pub fn set(&mut self, (x, y): GameBoardLocation, val: BoardSpace) {
+        self.cells[y][x] = val;
+        self.ages[y][x] = 0;
+    }
+
+    /// Gets the number of moves the tile at `loc` has gone without merging,
+    /// or `None` if the space is vacant or tiles don't
+    /// [decay](GameConfig::decay)
+    pub fn tile_age(&self, (x, y): GameBoardLocation) -> Option<usize> {
+        self.config.decay?;
+        self.cells[y][x].value().map(|_| self.ages[y][x])
+    }
+
+    /// Gets the number of moves left before the tile at `loc`
+    /// [decays](GameConfig::decay) if it doesn't merge, or `None` if the
+    /// space is vacant or tiles don't decay
+    ///
+    /// ```
+    /// use lib_2048::{
+    ///     BoardSpace::*, Decay, DecayFloor, GameBoard, GameConfig,
+    ///     MoveDirection,
+    /// };
+    ///
+    /// let config = GameConfig {
+    ///     decay: Some(Decay {
+    ///         after_moves: 2,
+    ///         floor: DecayFloor::Vanish,
+    ///     }),
+    ///     spawn_per_move: 0,
+    ///     ..Default::default()
+    /// };
+    /// let mut board = GameBoard::with_cells_and_config(
+    ///     config,
+    ///     Some([
+    ///         [Tile(8), Vacant, Vacant, Tile(2)],
+    ///         [Vacant, Vacant, Vacant, Vacant],
+    ///         [Vacant, Vacant, Vacant, Vacant],
+    ///         [Vacant, Vacant, Vacant, Vacant],
+    ///     ]),
+    ///     0,
+    /// );
+    ///
+    /// board.r#move(MoveDirection::Right);
+    /// assert_eq!(board.moves_until_decay((2, 0)), Some(1));
+    ///
+    /// // the 8 is halved, and the 2 can't be halved without going below the
+    /// // smallest tile that spawns
+    /// let outcome = board.r#move(MoveDirection::Left);
+    /// assert_eq!(outcome.decayed, 2);
+    /// assert_eq!(board.cells[0], [Tile(4), Vacant, Vacant, Vacant]);
+    /// assert_eq!(board.moves_until_decay((0, 0)), Some(2));
+    /// ```
+    pub fn moves_until_decay(&self, loc: GameBoardLocation) -> Option<usize> {
+        let after_moves = self.config.decay?.after_moves;
+        self.tile_age(loc)
+            .map(|age| after_moves.saturating_sub(age))
+    }
+
+    /// Gets the spaces orthogonally next to `loc` that are on the board, in
+    /// the order left, up, right, down
+    pub fn neighbors(
+        &self,
+        (x, y): GameBoardLocation,
+    ) -> impl Iterator<Item = (GameBoardLocation, BoardSpace)> + '_ {
+        let n = self.cells.len();
+
+        MoveDirection::all().into_iter().filter_map(move |dir| {
+            let (dx, dy) = dir.as_vector();
+            let loc = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+
+            (loc.0 < n && loc.1 < n).then(|| (loc, self.get(loc)))
+        })
+    }
+
+    /// Moves all tiles on the board, merging any adjacent tiles of the same numeric value
+    ///
+    /// If anything moved, [`spawn_per_move`](GameConfig::spawn_per_move) new
+    /// tiles are spawned afterwards.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, GameConfig, MoveDirection};
+    ///
+    /// let config = GameConfig {
+    ///     spawn_per_move: 2,
+    ///     ..Default::default()
+    /// };
+    /// let mut board = GameBoard::with_cells_and_config(
+    ///     config,
+    ///     Some([
+    ///         [Tile(2), Vacant, Vacant, Vacant],
+    ///         [Vacant, Vacant, Vacant, Vacant],
+    ///         [Vacant, Vacant, Vacant, Vacant],
+    ///         [Vacant, Vacant, Vacant, Vacant],
+    ///     ]),
+    ///     0,
+    /// );
+    ///
+    /// board.r#move(MoveDirection::Right);
+    /// assert_eq!(board.empty_count(), 13);
+    ///
+    /// // only one space is left after this move, so only one tile is spawned
+    /// let mut board = GameBoard::with_cells_and_config(
+    ///     board.config().clone(),
+    ///     Some([
+    ///         [Tile(2), Tile(2), Tile(8), Tile(16)],
+    ///         [Tile(4), Tile(2), Tile(4), Tile(2)],
+    ///         [Tile(2), Tile(4), Tile(2), Tile(4)],
+    ///         [Tile(4), Tile(2), Tile(4), Tile(2)],
+    ///     ]),
+    ///     0,
+    /// );
+    ///
+    /// board.r#move(MoveDirection::Left);
+    /// assert_eq!(board.empty_count(), 0);
+    /// ```
+    ///
+    /// Merges can also earn extra tiles with a
+    /// [`bonus_threshold`](GameConfig::bonus_threshold):
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, GameConfig, MoveDirection};
+    ///
+    /// let config = GameConfig {
+    ///     bonus_threshold: Some(128),
+    ///     ..Default::default()
+    /// };
+    /// let mut board = GameBoard::with_cells_and_config(
+    ///     config,
+    ///     Some([
+    ///         [Tile(64), Tile(64), Vacant, Vacant],
+    ///         [Vacant, Vacant, Vacant, Vacant],
+    ///         [Vacant, Vacant, Vacant, Vacant],
+    ///         [Vacant, Vacant, Vacant, Vacant],
+    ///     ]),
+    ///     0,
+    /// );
+    ///
+    /// let outcome = board.r#move(MoveDirection::Left);
+    /// assert_eq!(outcome.bonus_spawns, 1);
+    /// assert_eq!(board.empty_count(), 13);
+    /// ```
+    pub fn r#move(&mut self, dir: MoveDirection) -> MoveOutcome {
+        self.play(&[dir], None)
+    }
+
+    /// Makes a [`move`](Self::move), and gets the [`state`](Self::state) of
+    /// the game after it, including any tiles spawned by it
+    ///
+    /// A move that changes the board without ending the game leaves it
+    /// [`Playing`](GameState::Playing), so the board is only checked again
+    /// when that isn't the case.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, GameState, MoveDirection};
+    ///
+    /// let mut board = GameBoard::from([
+    ///     [Tile(2), Tile(2), Tile(8), Tile(16)],
+    ///     [Tile(8), Tile(16), Tile(32), Tile(64)],
+    ///     [Tile(16), Tile(32), Tile(64), Tile(128)],
+    ///     [Tile(32), Tile(64), Tile(128), Tile(256)],
+    /// ]);
+    ///
+    /// // the tile spawned in the top right corner can't merge with anything
+    /// let (outcome, state) = board.move_checked(MoveDirection::Left);
+    /// assert_eq!(outcome.score_delta, 4);
+    /// assert_eq!(state, GameState::Lost);
+    /// ```
+    pub fn move_checked(
+        &mut self,
+        dir: MoveDirection,
+    ) -> (MoveOutcome, GameState) {
+        let outcome = self.r#move(dir);
+
+        // a move is only made while the game is being played, so it's still
+        // being played unless that move ended it
+        let state = if outcome.moved && !outcome.just_ended {
+            GameState::Playing
+        } else {
+            self.state()
+        };
+
+        (outcome, state)
+    }
+
+    /// Makes every move in `s`, written as the first letters of their
+    /// directions (`u`, `d`, `l`, `r`) in either case, with any whitespace
+    /// ignored
+    ///
+    /// The whole string is checked before any moves are made, so the board
+    /// is left untouched if any character isn't a move.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard};
+    ///
+    /// let mut board = GameBoard::from([
+    ///     [Tile(2), Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ]);
+    ///
+    /// assert_eq!(board.apply_move_str("rr d").unwrap().len(), 3);
+    /// assert_eq!(board.apply_move_str("ux").unwrap_err().position, 1);
+    /// ```
+    pub fn apply_move_str(
+        &mut self,
+        s: &str,
+    ) -> Result<Vec<MoveOutcome>, ParseMoveError> {
+        let dirs = s
+            .chars()
+            .enumerate()
+            .filter(|(_, c)| !c.is_whitespace())
+            .map(|(position, c)| {
+                c.to_string()
+                    .parse::<MoveDirection>()
+                    .map_err(|e| ParseMoveError { position, ..e })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(dirs.into_iter().map(|dir| self.r#move(dir)).collect())
+    }
+
+    /// Rebuilds a game played with the [default](GameConfig::default) rules
+    /// from its moves and the tile spawned at each step, without using any
+    /// random numbers
+    ///
+    /// The first [`starting_tiles`](GameConfig::starting_tiles) spawns are
+    /// the tiles the game started with, and after that there's one spawn for
+    /// each move that changed the board. Moves that didn't change the board
+    /// don't use a spawn.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection, ReplayError};
+    ///
+    /// let moves = [MoveDirection::Left, MoveDirection::Left];
+    /// let spawns = [((1, 0), 2), ((3, 0), 2), ((0, 3), 4)];
+    ///
+    /// let board = GameBoard::replay_with_spawns(&moves, &spawns).unwrap();
+    /// assert_eq!(board.cells[0], [Tile(4), Vacant, Vacant, Vacant]);
+    /// assert_eq!(board.cells[3], [Tile(4), Vacant, Vacant, Vacant]);
+    ///
+    /// // the second move changes nothing, so it doesn't need a spawn, but the
+    /// // first one does
+    /// assert_eq!(
+    ///     GameBoard::replay_with_spawns(&moves, &spawns[..2]).unwrap_err(),
+    ///     ReplayError::TooFewSpawns {
+    ///         found: 2,
+    ///         moves_replayed: 0
+    ///     }
+    /// );
+    /// ```
+    pub fn replay_with_spawns(
+        moves: &[MoveDirection],
+        spawns: &[(GameBoardLocation, usize)],
+    ) -> Result<Self, ReplayError> {
+        let mut board = Self::empty();
+        let found = spawns.len();
+        let mut spawns = spawns.iter().copied().enumerate();
+
+        for _ in 0..board.config.starting_tiles {
+            let (index, (loc, value)) =
+                spawns.next().ok_or(ReplayError::TooFewSpawns {
+                    found,
+                    moves_replayed: 0,
+                })?;
+
+            if !is_vacant(&board.cells, loc) {
+                return Err(ReplayError::InvalidSpawn { index });
+            }
+            board.set(loc, BoardSpace::Tile(value));
+        }
+
+        for (i, dir) in moves.iter().enumerate() {
+            let after = board.preview_cells(*dir);
+
+            if after != board.cells {
+                let (index, (loc, value)) =
+                    spawns.next().ok_or(ReplayError::TooFewSpawns {
+                        found,
+                        moves_replayed: i,
+                    })?;
+
+                if !is_vacant(&after, loc) {
+                    return Err(ReplayError::InvalidSpawn { index });
+                }
+                board.set_spawn_queue(vec![(Some(loc), value)]);
+            }
+
+            board.r#move(*dir);
+        }
+
+        match spawns.len() {
+            0 => Ok(board),
+            unused => Err(ReplayError::TooManySpawns {
+                used: found - unused,
+                found,
+            }),
+        }
+    }
+
+    /// Works out what moving in `dir` would do, including the tiles it would
+    /// spawn, without changing the board yet
+    ///
+    /// The board after the move can be seen with [`staged`](Self::staged).
+    /// [`commit_staged`](Self::commit_staged) makes the move exactly as
+    /// previewed, spawns and all, and
+    /// [`cancel_staged`](Self::cancel_staged) throws it away. Staging another
+    /// move or making a move replaces the staged one. Nothing is staged for a
+    /// move that [can't be made](Self::try_move).
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection};
+    ///
+    /// let mut board = GameBoard::from([
+    ///     [Tile(2), Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ]);
+    ///
+    /// assert!(board.stage_move(MoveDirection::Right).is_some());
+    /// let preview = board.staged().unwrap().cells;
+    /// assert_eq!(board.get((0, 0)), Tile(2));
+    ///
+    /// board.commit_staged();
+    /// assert_eq!(board.cells, preview);
+    /// ```
+    pub fn stage_move(&mut self, dir: MoveDirection) -> Option<&MoveOutcome> {
+        self.staged = None;
+
+        let mut board = self.clone();
+        let outcome = board.try_move(dir).ok()?;
+
+        let staged =
+            self.staged.insert(Box::new(StagedMove { board, outcome }));
+        Some(&staged.outcome)
+    }
+
+    /// Gets what the board will look like once the
+    /// [staged move](Self::stage_move) is committed
+    pub fn staged(&self) -> Option<&GameBoard> {
+        self.staged.as_ref().map(|staged| &staged.board)
+    }
+
+    /// Makes the [staged move](Self::stage_move), returning its outcome, or
+    /// `None` if no move is staged
+    pub fn commit_staged(&mut self) -> Option<MoveOutcome> {
+        let staged = self.staged.take()?;
+        *self = staged.board;
+
+        Some(staged.outcome)
+    }
+
+    /// Throws away the [staged move](Self::stage_move) without making it
+    pub fn cancel_staged(&mut self) {
+        self.staged = None;
+    }
+
+    /// Moves like [`move`](Self::move), but fails instead of doing nothing
+    /// if the move wouldn't change the board or the game is over
+    ///
+    /// On failure, the board is left exactly as it was, and no tiles are
+    /// spawned.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, IllegalMove, MoveDirection};
+    ///
+    /// let mut board = GameBoard::from([
+    ///     [Tile(2), Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ]);
+    ///
+    /// assert_eq!(board.try_move(MoveDirection::Left), Err(IllegalMove));
+    /// assert!(board.try_move(MoveDirection::Right).is_ok());
+    /// ```
+    pub fn try_move(
+        &mut self,
+        dir: MoveDirection,
+    ) -> Result<MoveOutcome, IllegalMove> {
+        if self.is_over() || !self.is_legal_move(dir) {
+            return Err(IllegalMove);
+        }
+
+        Ok(self.r#move(dir))
+    }
+
+    /// Moves like [`try_move`](Self::try_move), but with the first tile
+    /// spawned afterwards placed at `spawn`'s location and value instead of
+    /// a random one
+    ///
+    /// Any other tiles the rules call for, like
+    /// [bonus tiles](GameConfig::bonus_threshold), are spawned as usual. The
+    /// tile is placed even when the rules wouldn't spawn anything after this
+    /// move. On failure, the board is left exactly as it was.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection, SpawnError};
+    ///
+    /// let mut board = GameBoard::from([
+    ///     [Tile(2), Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ]);
+    ///
+    /// // the tile slides into the space, so it's taken by the time the spawn
+    /// // is placed
+    /// assert_eq!(
+    ///     board.move_with_spawn(MoveDirection::Right, ((3, 0), 4)),
+    ///     Err(SpawnError::NotVacant)
+    /// );
+    ///
+    /// board.move_with_spawn(MoveDirection::Right, ((0, 0), 4)).unwrap();
+    /// assert_eq!(board.cells[0], [Tile(4), Vacant, Vacant, Tile(2)]);
+    /// ```
+    pub fn move_with_spawn(
+        &mut self,
+        dir: MoveDirection,
+        (loc, value): (GameBoardLocation, usize),
+    ) -> Result<MoveOutcome, SpawnError> {
+        if self.is_over() || !self.is_legal_move(dir) {
+            return Err(SpawnError::IllegalMove);
+        }
+
+        if !is_vacant(&self.preview_cells(dir), loc) {
+            return Err(SpawnError::NotVacant);
+        }
+
+        Ok(self.play(&[dir], Some((loc, value))))
+    }
+
+    /// Slides toward `corner` with two moves, one vertical and one
+    /// horizontal, in the order given by [`Corner::directions`]
+    ///
+    /// New tiles are only spawned once, after both moves.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, Corner, GameBoard};
+    ///
+    /// let mut board = GameBoard::from([
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Tile(2), Vacant],
+    ///     [Vacant, Vacant, Tile(2), Vacant],
+    /// ]);
+    ///
+    /// let outcome = board.move_to_corner(Corner::TopLeft);
+    /// assert!(outcome.moved);
+    /// assert_eq!(outcome.score_delta, 4);
+    /// assert_eq!(board.get((0, 0)), Tile(4));
+    /// ```
+    pub fn move_to_corner(&mut self, corner: Corner) -> MoveOutcome {
+        self.play(&corner.directions(), None)
+    }
+
+    /// Picks the next direction from the configured
+    /// [`direction_cycle`](GameConfig::direction_cycle), skipping any that
+    /// wouldn't change the board
+    ///
+    /// If no direction in the cycle is legal, the next one is returned
+    /// anyway, and moving in it does nothing.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection};
+    ///
+    /// let mut board = GameBoard::from([
+    ///     [Tile(2), Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ]);
+    ///
+    /// // left and up are skipped, since the tile is already top-left
+    /// assert_eq!(board.next_scripted_direction(), MoveDirection::Right);
+    /// ```
+    pub fn next_scripted_direction(&mut self) -> MoveDirection {
+        let all = MoveDirection::all();
+        let cycle = match self.config.direction_cycle.as_slice() {
+            [] => &all[..],
+            cycle => cycle,
+        };
+
+        let start = self.cycle_pos % cycle.len();
+        let offset = (0..cycle.len())
+            .find(|i| self.is_legal_move(cycle[(start + i) % cycle.len()]))
+            .unwrap_or(0);
+
+        let dir = cycle[(start + offset) % cycle.len()];
+        self.cycle_pos = start + offset + 1;
+
+        dir
+    }
+
+    /// Makes a single move out of sliding in each of `dirs` in order,
+    /// placing `spawn` before any random tiles if it's given
+    fn play(
+        &mut self,
+        dirs: &[MoveDirection],
+        spawn: Option<(GameBoardLocation, usize)>,
+    ) -> MoveOutcome {
+        self.last_spawns.clear();
+        self.staged = None;
+
+        if self.is_over() {
+            return MoveOutcome::default();
+        }
+
+        let was_won = self.ever_won;
+        let before = self.turn();
+
+        self.age_tiles();
+
+        let mut outcome = MoveOutcome::default();
+        for dir in dirs {
+            if self.config.allows(*dir) {
+                outcome.absorb(self.slide(*dir));
+            }
+        }
+
+        if outcome.moved {
+            self.history.push(before);
+        } else {
+            self.ages = before.ages;
+        }
+
+        self.finish_move(&mut outcome, was_won, spawn);
+        outcome
+    }
+
+    /// Ages every tile by a move, while tiles [decay](GameConfig::decay)
+    ///
+    /// This comes before sliding, so any tiles that merge start again from 0.
+    fn age_tiles(&mut self) {
+        if self.config.decay.is_none() {
+            return;
+        }
+
+        for (age, space) in self
+            .ages
+            .iter_mut()
+            .flatten()
+            .zip(self.cells.iter().flatten())
+        {
+            if *space != BoardSpace::Vacant {
+                *age += 1;
+            }
+        }
+    }
+
+    fn turn(&self) -> Turn {
+        Turn {
+            cells: self.cells,
+            score: self.score,
+            score_breakdown: self.score_breakdown.clone(),
+            biggest_merge: self.biggest_merge,
+            moves: self.moves,
+            ever_won: self.ever_won,
+            rng: self.rng.clone(),
+            ages: self.ages,
+            last_merge: self.last_merge,
+        }
+    }
+
+    /// Takes back the last move that changed the board, along with the tiles
+    /// it spawned
+    ///
+    /// Each successful undo uses up one of the configured
+    /// [`undos`](GameConfig::undos). Moving again after an undo spawns the
+    /// same tiles as before, so undoing can't be used to reroll spawns.
+    ///
+    /// ```
+    /// use lib_2048::{
+    ///     BoardSpace::*, GameBoard, GameConfig, MoveDirection, UndoError,
+    /// };
+    ///
+    /// let cells = [
+    ///     [Tile(2), Tile(2), Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ];
+    /// let config = GameConfig {
+    ///     undos: Some(1),
+    ///     ..Default::default()
+    /// };
+    /// let mut board = GameBoard::with_cells_and_config(config, Some(cells), 0);
+    ///
+    /// assert_eq!(board.undo(), Err(UndoError::NothingToUndo));
+    ///
+    /// board.r#move(MoveDirection::Left);
+    /// board.r#move(MoveDirection::Right);
+    /// assert_eq!(board.undo(), Ok(()));
+    /// assert_eq!(board.undos_remaining(), Some(0));
+    /// assert_eq!(board.undo(), Err(UndoError::NoUndosLeft));
+    /// ```
+    pub fn undo(&mut self) -> Result<(), UndoError> {
+        if self.undos_remaining == Some(0) {
+            return Err(UndoError::NoUndosLeft);
+        }
+
+        let turn = self.history.pop().ok_or(UndoError::NothingToUndo)?;
+
+        // only moves record where the highest tile ended up
+        if turn.moves < self.moves {
+            self.max_tile_positions.pop();
+        }
+
+        self.cells = turn.cells;
+        self.score = turn.score;
+        self.score_breakdown = turn.score_breakdown;
+        self.biggest_merge = turn.biggest_merge;
+        self.moves = turn.moves;
+        self.ever_won = turn.ever_won;
+        self.rng = turn.rng;
+        self.ages = turn.ages;
+        self.last_merge = turn.last_merge;
+        self.version += 1;
+        self.last_spawns.clear();
+
+        if let Some(remaining) = &mut self.undos_remaining {
+            *remaining -= 1;
+        }
+
+        Ok(())
+    }
+
+    /// Gets the number of [undos](Self::undo) left in this game, or `None`
+    /// if there's no limit
+    pub fn undos_remaining(&self) -> Option<usize> {
+        self.undos_remaining
+    }
+
+    /// Slides and merges all tiles in `dir` without spawning any new tiles
+    fn slide(&mut self, dir: MoveDirection) -> MoveOutcome {
+        let before = self.cells;
+        let outcome = slide_cells(&mut self.cells, dir, &self.config);
+        self.add_merges(&outcome);
+        self.slide_ages(&before, dir);
+
+        outcome
+    }
+
+    /// Moves each tile's age along with it, now that the tiles in `before`
+    /// have slid in `dir`, with the tiles that merged starting again from 0
+    fn slide_ages(&mut self, before: &GameBoardCells, dir: MoveDirection) {
+        if self.config.decay.is_none() {
+            return;
+        }
+
+        let mut lines: [[_; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] =
+            std::array::from_fn(|y| {
+                std::array::from_fn(|x| {
+                    (before[y][x], self.ages[y][x], self.cells[y][x])
+                })
+            });
+
+        for_each_line(&mut lines, dir, |line| {
+            let tiles: Vec<_> = line
+                .iter()
+                .filter_map(|(space, age, _)| space.value().map(|v| (v, *age)))
+                .collect();
+
+            // tiles keep their order as they slide, so each tile after the
+            // slide is the next one from before it, or a pair that merged.
+            // A tile that merged around the edge of a wrapping line took the
+            // last one with it.
+            let (mut front, mut back) = (0, tiles.len());
+            for (_, age, after) in line.iter_mut() {
+                *age = match after.value() {
+                    None => 0,
+                    Some(value) => {
+                        let (first, first_age) = tiles[front];
+                        front += 1;
+
+                        if value == first {
+                            first_age
+                        } else if front < back && tiles[front].0 == first {
+                            front += 1;
+                            0
+                        } else {
+                            back -= 1;
+                            0
+                        }
+                    },
+                };
+            }
+        });
+
+        self.ages = lines.map(|row| row.map(|(_, age, _)| age));
+    }
+
+    /// Halves every tile that's gone [`after_moves`](Decay::after_moves)
+    /// moves without merging, returning how many decayed
+    fn decay_tiles(&mut self, decay: Decay) -> usize {
+        let smallest = self
+            .config
+            .spawn_distribution
+            .iter()
+            .map(|(value, _)| *value)
+            .min()
+            .unwrap_or(1);
+
+        let mut decayed = 0;
+        for (age, space) in self
+            .ages
+            .iter_mut()
+            .flatten()
+            .zip(self.cells.iter_mut().flatten())
+        {
+            let t = match space {
+                BoardSpace::Tile(t) if *age >= decay.after_moves => *t,
+                _ => continue,
+            };
+
+            *space = if t / 2 >= smallest {
+                BoardSpace::Tile(t / 2)
+            } else {
+                match decay.floor {
+                    DecayFloor::Vanish => BoardSpace::Vacant,
+                    DecayFloor::Keep => BoardSpace::Tile(t.min(smallest)),
+                }
+            };
+            *age = 0;
+            decayed += 1;
+        }
+
+        decayed
+    }
+
+    /// Adds the merges from `outcome` to the score and merge stats
+    fn add_merges(&mut self, outcome: &MoveOutcome) {
+        self.score += outcome.score_delta;
+
+        for value in &outcome.merged_values {
+            *self.score_breakdown.entry(*value).or_default() += value;
+            self.biggest_merge = self.biggest_merge.max(*value);
+        }
+    }
+
+    /// Slides all tiles on the board in `dir` without merging them or
+    /// spawning new tiles, returning whether any tile moved
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection};
+    ///
+    /// let mut board = GameBoard::from([
+    ///     [Tile(2), Vacant, Tile(2), Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ]);
+    ///
+    /// assert!(board.apply_gravity(MoveDirection::Right));
+    /// assert_eq!(board.cells[0], [Vacant, Vacant, Tile(2), Tile(2)]);
+    /// assert!(!board.apply_gravity(MoveDirection::Right));
+    /// ```
+    pub fn apply_gravity(&mut self, dir: MoveDirection) -> bool {
+        self.last_spawns.clear();
+        let mut moved = false;
+
+        let before = self.cells;
+        for_each_line(&mut self.cells, dir, |row| {
+            moved |= compact_row(row);
+        });
+        self.slide_ages(&before, dir);
+
+        if moved {
+            self.version += 1;
+        }
+
+        moved
+    }
+
+    /// Slides and merges all tiles in `dir` without spawning any new tiles,
+    /// until a slide merges nothing, returning the number of slides that
+    /// merged something
+    ///
+    /// Under the default rules this is a single slide, where a merged tile
+    /// can't merge again. With [`cascade_merges`](GameConfig::cascade_merges)
+    /// set, this is a sandbox for trying out rules where merges cascade, so
+    /// a row of four `2`s becomes an `8`. Merges add to the score like a
+    /// move and can be [undone](Self::undo), but don't count as
+    /// [moves](Self::moves).
+    ///
+    /// This makes at most one slide per space on the board, or
+    /// [`GAME_BOARD_SIZE`] squared. Every slide that merges removes a tile,
+    /// so the board always settles before that limit is reached.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, GameConfig, MoveDirection};
+    ///
+    /// let cells = [
+    ///     [Tile(2), Tile(2), Tile(2), Tile(2)],
+    ///     [Tile(2), Tile(4), Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ];
+    ///
+    /// let mut board = GameBoard::from(cells);
+    /// assert_eq!(board.merge_until_stable(MoveDirection::Left), 1);
+    /// assert_eq!(board.cells[0], [Tile(4), Tile(4), Vacant, Vacant]);
+    ///
+    /// let config = GameConfig {
+    ///     cascade_merges: true,
+    ///     ..Default::default()
+    /// };
+    /// let mut board =
+    ///     GameBoard::with_cells_and_config(config, Some(cells), 0);
+    /// assert_eq!(board.merge_until_stable(MoveDirection::Left), 2);
+    /// assert_eq!(board.cells[0], [Tile(8), Vacant, Vacant, Vacant]);
+    /// assert_eq!(board.cells[1], [Tile(2), Tile(4), Vacant, Vacant]);
+    /// assert_eq!(board.merge_until_stable(MoveDirection::Left), 0);
+    /// ```
+    pub fn merge_until_stable(&mut self, dir: MoveDirection) -> usize {
+        let max_passes = if self.config.cascade_merges {
+            GAME_BOARD_SIZE * GAME_BOARD_SIZE
+        } else {
+            1
+        };
+
+        let before = self.turn();
+        let mut passes = 0;
+
+        while passes < max_passes {
+            if self.slide(dir).merges == 0 {
+                break;
+            }
+
+            passes += 1;
+        }
+
+        if self.cells != before.cells {
+            self.history.push(before);
+            self.version += 1;
+            self.last_spawns.clear();
+        }
+
+        passes
+    }
+
+    /// Merges the tile at `b` into the tile at `a` if the
+    /// [rules](GameConfig::can_merge) allow it, returning whether they
+    /// merged
+    ///
+    /// This is for setting up exact merges in tests and scripted scenarios
+    /// without making a whole move. The merge is scored the same way as one
+    /// made by a [move](Self::move), but nothing slides or spawns. With
+    /// [`wrap`](GameConfig::wrap) set, spaces at opposite ends of a row or
+    /// column count as next to each other.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MergeError};
+    ///
+    /// let mut board = GameBoard::from([
+    ///     [Tile(4), Tile(4), Tile(2), Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ]);
+    ///
+    /// assert_eq!(board.try_merge_cells((0, 0), (1, 0)), Ok(true));
+    /// assert_eq!(board.cells[0], [Tile(8), Vacant, Tile(2), Vacant]);
+    /// assert_eq!(board.score, 8);
+    ///
+    /// assert_eq!(board.try_merge_cells((0, 0), (1, 0)), Ok(false));
+    /// assert_eq!(
+    ///     board.try_merge_cells((0, 0), (2, 0)),
+    ///     Err(MergeError::NotAdjacent)
+    /// );
+    /// ```
+    pub fn try_merge_cells(
+        &mut self,
+        a: GameBoardLocation,
+        b: GameBoardLocation,
+    ) -> Result<bool, MergeError> {
+        let n = self.cells.len();
+        if [a, b].iter().any(|(x, y)| *x >= n || *y >= n) {
+            return Err(MergeError::OutOfBounds);
+        }
+
+        let wraps = |p: usize, q: usize| p.min(q) == 0 && p.max(q) == n - 1;
+        let adjacent = self.neighbors(a).any(|(loc, _)| loc == b)
+            || (self.config.wrap
+                && ((a.1 == b.1 && wraps(a.0, b.0))
+                    || (a.0 == b.0 && wraps(a.1, b.1))));
+        if !adjacent {
+            return Err(MergeError::NotAdjacent);
+        }
+
+        let t = match (self.get(a), self.get(b)) {
+            (BoardSpace::Tile(t), BoardSpace::Tile(t2))
+                if self.config.can_merge(t, t2) =>
+            {
+                t
+            },
+            _ => return Ok(false),
+        };
+
+        let mut outcome = MoveOutcome::default();
+        outcome.record_merge(t * 2);
+        self.set(a, BoardSpace::Tile(t * 2));
+        self.set(b, BoardSpace::Vacant);
+
+        self.add_merges(&outcome);
+        self.version += 1;
+        self.last_spawns.clear();
+
+        Ok(true)
+    }
+
+    /// Empties every space in row `y`, for power-ups that clear a line,
+    /// returning the total value of the tiles cleared, or `None` if the row
+    /// isn't on the board
+    ///
+    /// If `add_to_score` is set, the cleared tiles are added to the score.
+    /// This isn't a move, so nothing spawns afterwards, but the cleared
+    /// spaces can be spawned into by the next move.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard};
+    ///
+    /// let mut board = GameBoard::from([
+    ///     [Tile(2), Tile(4), Vacant, Tile(8)],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ]);
+    ///
+    /// assert_eq!(board.clear_row(0, true), Some(14));
+    /// assert_eq!(board.cells[0], [Vacant; 4]);
+    /// assert_eq!(board.score, 14);
+    /// assert_eq!(board.clear_row(4, true), None);
+    /// ```
+    pub fn clear_row(&mut self, y: usize, add_to_score: bool) -> Option<usize> {
+        let n = self.cells.len();
+        let line = (0..n).map(|x| (x, y));
+        (y < n).then(|| self.clear_spaces(line, add_to_score))
+    }
+
+    /// Empties every space in column `x`, like [`clear_row`](Self::clear_row)
+    pub fn clear_column(
+        &mut self,
+        x: usize,
+        add_to_score: bool,
+    ) -> Option<usize> {
+        let n = self.cells.len();
+        let line = (0..n).map(|y| (x, y));
+        (x < n).then(|| self.clear_spaces(line, add_to_score))
+    }
+
+    /// Empties each of `locs`, returning the total value of the tiles that
+    /// were there
+    fn clear_spaces(
+        &mut self,
+        locs: impl Iterator<Item = GameBoardLocation>,
+        add_to_score: bool,
+    ) -> usize {
+        let mut cleared = 0;
+        let mut changed = false;
+        for loc in locs {
+            if let Some(value) = self.get(loc).value() {
+                cleared += value;
+                changed = true;
+                self.set(loc, BoardSpace::Vacant);
+            }
+        }
+
+        if add_to_score {
+            self.score += cleared;
+        }
+        if changed {
+            self.version += 1;
+            self.last_spawns.clear();
+        }
+
+        cleared
+    }
+
+    /// Finishes a move that slid the board, spawning new tiles if anything
+    /// moved
+    fn finish_move(
+        &mut self,
+        outcome: &mut MoveOutcome,
+        was_won: bool,
+        spawn: Option<(GameBoardLocation, usize)>,
+    ) {
+        if outcome.moved {
+            let spawns = self.settle_move(outcome);
+            self.spawn_tracked(spawns, spawn);
+
+            let combo_score = match self.config.combo {
+                Some(combo) if outcome.combo => {
+                    outcome.score_delta * combo.multiplier.saturating_sub(1)
+                },
+                _ => 0,
+            };
+            let spawn_score: usize = self
+                .last_spawns
+                .iter()
+                .filter_map(|loc| self.get(*loc).value())
+                .map(|value| self.config.spawn_score.points(value))
+                .sum();
+            outcome.extra_score =
+                self.config.per_move_score + spawn_score + combo_score;
+            self.score += outcome.extra_score;
+
+            if let Some(loc) = self.highest_tile_position() {
+                self.max_tile_positions.push(loc);
+            }
+
+            if let Some(timer) = &mut self.timer {
+                timer.record_move();
+            }
+        }
+
+        outcome.just_won = self.config.win_behavior == WinBehavior::Prompt
+            && !was_won
+            && self.has_winning_tile();
+        self.ever_won |= self.has_winning_tile();
+        outcome.just_ended = self.is_over();
+        if outcome.just_ended {
+            self.session_best = self.session_best.max(self.score);
+        }
+    }
+
+    /// Counts a move that slid the board, then decays tiles and checks for a
+    /// combo, returning how many tiles should spawn after it
+    fn settle_move(&mut self, outcome: &mut MoveOutcome) -> usize {
+        if let Some(threshold) = self.config.bonus_threshold {
+            outcome.bonus_spawns = outcome
+                .merged_values
+                .iter()
+                .filter(|value| **value >= threshold)
+                .count();
+        }
+
+        self.moves += 1;
+        self.version += 1;
+
+        if let Some(decay) = self.config.decay {
+            outcome.decayed = self.decay_tiles(decay);
+        }
+
+        if let Some(combo) = self.config.combo {
+            outcome.combo = self.continues_combo(combo);
+
+            if outcome.merges > 0 {
+                self.last_merge = Some(LastMerge {
+                    moves: self.moves,
+                    at: self.timer.as_ref().map(MoveTimer::now),
+                });
+            }
+        }
+
+        let spawns = if outcome.combo {
+            0
+        } else {
+            self.config.spawn_per_move
+        };
+
+        spawns + outcome.bonus_spawns
+    }
+
+    /// Checks if a move that's just been made is within the window of the
+    /// last one that merged tiles
+    fn continues_combo(&self, combo: Combo) -> bool {
+        let last = match self.last_merge {
+            Some(last) => last,
+            None => return false,
+        };
+
+        match combo.window {
+            ComboWindow::Moves(moves) => self.moves - last.moves <= moves,
+            ComboWindow::Time(window) => match (&self.timer, last.at) {
+                (Some(timer), Some(at)) => {
+                    timer.now().saturating_sub(at) <= window
+                },
+                _ => false,
+            },
+        }
+    }
+
+    /// Spawns up to `count` tiles, remembering where they went so they can be
+    /// [rerolled](Self::reroll_spawn)
+    fn spawn_tracked(
+        &mut self,
+        count: usize,
+        chosen: Option<(GameBoardLocation, usize)>,
+    ) {
+        let vacant = self.all_empty_spaces();
+
+        // a chosen tile takes the place of the first one that would have
+        // spawned, and is placed even if the rules wouldn't spawn any
+        let count = match chosen {
+            Some((loc, value)) => {
+                self.set(loc, BoardSpace::Tile(value));
+                count.saturating_sub(1)
+            },
+            None => count,
+        };
+        self.add_random_tiles(count);
+
+        self.last_spawns = vacant
+            .into_iter()
+            .filter(|loc| self.get(*loc) != BoardSpace::Vacant)
+            .collect();
+    }
+
+    /// Replaces the tiles spawned by the last move with new random ones,
+    /// returning whether anything was rerolled
+    ///
+    /// This only works right after a move that spawned tiles, and only once
+    /// per move. The merges and score from the move are kept, including any
+    /// [`spawn_score`](GameConfig::spawn_score) for the replaced tiles. The
+    /// new tiles may spawn in the same spaces as the ones they replace.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection};
+    ///
+    /// let mut board = GameBoard::from([
+    ///     [Tile(2), Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ]);
+    /// assert!(!board.reroll_spawn());
+    ///
+    /// board.r#move(MoveDirection::Right);
+    /// assert!(board.reroll_spawn());
+    /// assert_eq!(board.empty_count(), 14);
+    ///
+    /// assert!(!board.reroll_spawn());
+    /// ```
+    pub fn reroll_spawn(&mut self) -> bool {
+        let spawns = std::mem::take(&mut self.last_spawns);
+        if spawns.is_empty() {
+            return false;
+        }
+
+        for loc in &spawns {
+            self.set(*loc, BoardSpace::Vacant);
+        }
+        self.add_random_tiles(spawns.len());
+        self.version += 1;
+
+        // a new tile can take over as the highest one, or stop being it
+        let highest = self.highest_tile_position();
+        if let (Some(last), Some(loc)) =
+            (self.max_tile_positions.last_mut(), highest)
+        {
+            *last = loc;
+        }
+
+        true
+    }
+
+    /// Ends the game early, no matter the state of the board
+    ///
+    /// Once ended, [`is_over`](Self::is_over) is always `true` and moves no
+    /// longer change the board.
+    pub fn end_game(&mut self) {
+        self.ended = true;
+        self.session_best = self.session_best.max(self.score);
+    }
+
+    /// Starts a new game with the same rules, keeping the
+    /// [`session_best`](Self::session_best)
+    ///
+    /// The new game's tiles come from the same random number generator, so
+    /// a [seeded](GameConfig::seed) session plays out the same every time
+    /// without each game being a copy of the last. If
+    /// [timing](Self::enable_timing) is on, the timer starts again on the
+    /// same clock, and the [version](Self::version) keeps counting up from
+    /// the last game's.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection};
+    ///
+    /// let mut board = GameBoard::from([
+    ///     [Tile(2), Tile(2), Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ]);
+    /// board.r#move(MoveDirection::Left);
+    /// assert_eq!(board.session_best(), 0);
+    ///
+    /// board.restart();
+    /// assert_eq!(board.score, 0);
+    /// assert_eq!(board.session_best(), 4);
+    /// ```
+    pub fn restart(&mut self) {
+        let session_best = self.session_best.max(self.score);
+        let version = self.version + 1;
+        let timer = self.timer.as_ref().map(MoveTimer::restarted);
+
+        *self =
+            Self::with_parts(self.config.clone(), None, 0, self.rng.clone());
+        self.session_best = session_best;
+        self.version = version;
+        self.timer = timer;
+    }
+
+    /// Gets the highest score of the games in this session that have been
+    /// [restarted](Self::restart) or have ended, or `0` if none have
+    ///
+    /// The score of the game being played only counts once it's over.
+    pub fn session_best(&self) -> usize {
+        self.session_best
+    }
+
+    /// Checks if the game is over, because it was won with
+    /// [`WinBehavior::Stop`], lost, hit its
+    /// [move limit](GameConfig::move_limit), or was
+    /// [ended early](Self::end_game)
+    ///
+    /// Once the game is over, moves no longer change the board.
+    pub fn is_over(&self) -> bool {
+        self.state() != GameState::Playing
+    }
+
+    /// Gets whether the game is still being played, and how it finished if
+    /// not
+    ///
+    /// When more than one way of finishing applies at once, the first one
+    /// in this list is returned:
+    ///
+    /// 1. [`Lost`](GameState::Lost), if no move can change the board
+    ///    (including when there are no tiles on it) or the game was
+    ///    [ended early](Self::end_game)
+    /// 2. [`LimitReached`](GameState::LimitReached), if the
+    ///    [move limit](GameConfig::move_limit) was used up
+    /// 3. [`Won`](GameState::Won), if the
+    ///    [win target](GameConfig::win_target) was reached with
+    ///    [`WinBehavior::Stop`]. With any other behavior, a won game is
+    ///    still [`Playing`](GameState::Playing).
+    ///
+    /// Otherwise, the game is [`Playing`](GameState::Playing).
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, GameState};
+    ///
+    /// let board = GameBoard::from([
+    ///     [Tile(2048), Tile(4), Tile(2), Tile(4)],
+    ///     [Tile(4), Tile(2), Tile(4), Tile(2)],
+    ///     [Tile(2), Tile(4), Tile(2), Tile(4)],
+    ///     [Tile(4), Tile(2), Tile(4), Tile(2)],
+    /// ]);
+    ///
+    /// assert!(board.has_won());
+    /// assert_eq!(board.state(), GameState::Lost);
+    /// ```
+    pub fn state(&self) -> GameState {
+        if self.ended || self.has_lost() {
+            GameState::Lost
+        } else if self.limit_reached() {
+            GameState::LimitReached
+        } else if self.config.win_behavior == WinBehavior::Stop
+            && self.has_won()
+        {
+            GameState::Won
+        } else {
+            GameState::Playing
+        }
+    }
+
+    /// Checks if every move allowed by the
+    /// [move limit](GameConfig::move_limit) has been made
+    pub fn limit_reached(&self) -> bool {
+        self.config
+            .move_limit
+            .is_some_and(|limit| self.moves >= limit)
+    }
+
+    /// Gets the largest tile value that can theoretically be made on this
+    /// board
+    ///
+    /// Building a tile of `2^k` requires holding a chain of smaller tiles
+    /// (`2^(k-1)`, `2^(k-2)`, ...) on the board at the same time, one per cell.
+    /// With every cell filled by such a chain and a `4` spawning into the last
+    /// free cell, the largest reachable tile is `2^(cells + 1)`, which is
+    /// `131072` on a 4x4 board.
+    pub fn max_achievable_tile(&self) -> usize {
+        let n = self.cells.len();
+        1 << (n * n + 1)
+    }
+
+    /// Gets the points scored by merges on this board, keyed by the value of
+    /// the tile each merge made
+    ///
+    /// Only points from merges made on this board are counted. A score the
+    /// board started with, [`per_move_score`](GameConfig::per_move_score),
+    /// [`spawn_score`](GameConfig::spawn_score), the extra points from a
+    /// [combo](GameConfig::combo)'s multiplier, and spaces cleared by
+    /// [`clear_row`](Self::clear_row) or [`clear_column`](Self::clear_column)
+    /// are all left out, so the values only add up to
+    /// [`score`](Self::score) when none of those scored anything.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection};
+    ///
+    /// let mut board = GameBoard::from([
+    ///     [Tile(2), Tile(2), Tile(4), Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ]);
+    ///
+    /// board.r#move(MoveDirection::Left);
+    /// board.r#move(MoveDirection::Left);
+    ///
+    /// assert_eq!(board.score_breakdown().get(&4), Some(&4));
+    /// assert_eq!(board.score_breakdown().get(&8), Some(&8));
+    /// ```
+    pub fn score_breakdown(&self) -> &BTreeMap<usize, usize> {
+        &self.score_breakdown
+    }
+
+    /// Gets the value of the biggest tile made by a merge on this board, or
+    /// `0` if nothing has merged yet
+    ///
+    /// Unlike [`highest_tile`](Self::highest_tile), this only counts merges,
+    /// not tiles the board started with or spawned.
+    pub fn biggest_merge(&self) -> usize {
+        self.biggest_merge
+    }
+
+    /// Computes a checksum of the board's cells, score, and move count, for
+    /// detecting corrupted or tampered saves
+    ///
+    /// This is a 32-bit FNV-1a hash over every value as a little-endian
+    /// `u64`, so it's the same on every platform.
+    pub fn checksum(&self) -> u32 {
+        const FNV_OFFSET_BASIS: u32 = 0x811c9dc5;
+        const FNV_PRIME: u32 = 0x01000193;
+
+        let values = self
+            .cells
+            .iter()
+            .flatten()
+            .map(|space| space.value().unwrap_or(0))
+            .chain([self.score, self.moves]);
+
+        let mut hash = FNV_OFFSET_BASIS;
+
+        for value in values {
+            for byte in (value as u64).to_le_bytes() {
+                hash ^= u32::from(byte);
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        }
+
+        hash
+    }
+
+    /// Gets the number of moves made that changed the board
+    pub fn moves(&self) -> usize {
+        self.moves
+    }
+
+    /// Gets a number that goes up every time a move changes the board
+    ///
+    /// A UI can hold on to the version it showed a player and hand it to
+    /// [`check_version`](Self::check_version) before making their move, so a
+    /// move made against an outdated view of the board can be turned away.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Checks that the board hasn't been moved since it was at `expected`
+    /// [`version`](Self::version)
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection};
+    ///
+    /// let mut board = GameBoard::from([
+    ///     [Tile(2), Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ]);
+    /// let seen = board.version();
+    ///
+    /// // nothing moves, so the version stays the same
+    /// board.r#move(MoveDirection::Left);
+    /// assert!(board.check_version(seen));
+    ///
+    /// board.r#move(MoveDirection::Right);
+    /// assert!(!board.check_version(seen));
+    /// ```
+    pub fn check_version(&self, expected: u64) -> bool {
+        self.version == expected
+    }
+
+    /// Gets the value of the highest tile on the board, or `None` if the board
+    /// is empty
+    pub fn highest_tile(&self) -> Option<usize> {
+        self.cells
+            .iter()
+            .flatten()
+            .filter_map(|space| match space {
+                BoardSpace::Tile(t) => Some(*t),
+                BoardSpace::Vacant => None,
+            })
+            .max()
+    }
+
+    /// Gets where the highest tile was after each move that changed the
+    /// board, oldest first
+    ///
+    /// When several tiles share the highest value, the first one reading
+    /// left to right from the top row is used. [Undoing](Self::undo) a move
+    /// removes its entry.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection};
+    ///
+    /// let mut board = GameBoard::empty();
+    /// board.set((1, 1), Tile(8));
+    /// board.set_spawn_queue(vec![(Some((3, 3)), 2); 2]);
+    ///
+    /// board.r#move(MoveDirection::Left);
+    /// board.r#move(MoveDirection::Up);
+    ///
+    /// assert_eq!(board.max_tile_positions(), [(0, 1), (0, 0)]);
+    /// ```
+    pub fn max_tile_positions(&self) -> &[GameBoardLocation] {
+        &self.max_tile_positions
+    }
+
+    /// Gets the location of the highest tile, picking the first in row-major
+    /// order if there's a tie
+    fn highest_tile_position(&self) -> Option<GameBoardLocation> {
+        let highest = self.highest_tile()?;
+        self.spaces()
+            .find(|(_, space)| *space == BoardSpace::Tile(highest))
+            .map(|(loc, _)| loc)
+    }
+
+    /// Gets the number of vacant spaces on the board
+    pub fn empty_count(&self) -> usize {
+        self.cells
+            .iter()
+            .flatten()
+            .filter(|space| **space == BoardSpace::Vacant)
+            .count()
+    }
+
+    /// Gets the fraction of spaces on the board holding a tile, from `0.0`
+    /// for an empty board to `1.0` for a full one
+    ///
+    /// ```
+    /// use lib_2048::GameBoard;
+    ///
+    /// // 2 of the 16 spaces start with a tile
+    /// assert_eq!(GameBoard::new().fullness(), 0.125);
+    /// ```
+    pub fn fullness(&self) -> f64 {
+        let spaces = self.cells.len() * self.cells.len();
+        (spaces - self.empty_count()) as f64 / spaces as f64
+    }
+
+    /// Gets the number of vacant spaces in the largest group of vacant
+    /// spaces that are connected orthogonally
+    pub fn largest_empty_region(&self) -> usize {
+        let mut seen = [[false; GAME_BOARD_SIZE]; GAME_BOARD_SIZE];
+        let mut largest = 0;
+
+        for start in self.all_empty_spaces() {
+            if seen[start.1][start.0] {
+                continue;
+            }
+
+            seen[start.1][start.0] = true;
+            let mut size = 0;
+            let mut stack = vec![start];
+
+            while let Some(loc) = stack.pop() {
+                size += 1;
+
+                for ((x, y), space) in self.neighbors(loc) {
+                    if space == BoardSpace::Vacant && !seen[y][x] {
+                        seen[y][x] = true;
+                        stack.push((x, y));
+                    }
+                }
+            }
+
+            largest = largest.max(size);
+        }
+
+        largest
+    }
+
+    /// Gets the number of different tile values on the board
+    ///
+    /// Vacant spaces aren't counted, and tiles with the same value are only
+    /// counted once.
+    pub fn distinct_tile_values(&self) -> usize {
+        self.cells
+            .iter()
+            .flatten()
+            .filter_map(BoardSpace::value)
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+
+    /// Gets the values of every tile on the board, highest first
+    ///
+    /// Tiles with the same value are all included, so the first few entries
+    /// are the board's biggest tiles.
+    pub fn tiles_sorted_desc(&self) -> Vec<usize> {
+        let mut tiles = self
+            .cells
+            .iter()
+            .flatten()
+            .filter_map(BoardSpace::value)
+            .collect::<Vec<_>>();
+        tiles.sort_unstable_by(|a, b| b.cmp(a));
+        tiles
+    }
+
+    /// Checks if a tile of at least the configured
+    /// [`win_target`](GameConfig::win_target) is on the board, the same as
+    /// [`has_winning_tile`](Self::has_winning_tile)
+    pub fn has_won(&self) -> bool {
+        self.has_winning_tile()
+    }
+
+    /// Checks if a tile of at least the configured
+    /// [`win_target`](GameConfig::win_target) is on the board right now
+    ///
+    /// A winning tile that merges into a bigger one still counts, since the
+    /// bigger tile is past the target too. Use [`ever_won`](Self::ever_won)
+    /// to check if the game was won at any point.
+    pub fn has_winning_tile(&self) -> bool {
+        self.highest_tile()
+            .is_some_and(|t| t >= self.config.win_target)
+    }
+
+    /// Checks if a winning tile has been on the board at any point in this
+    /// game, even if it's gone now
+    ///
+    /// [Undoing](Self::undo) the move that first reached the
+    /// [`win_target`](GameConfig::win_target) takes the win back.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection};
+    ///
+    /// let mut board = GameBoard::from([
+    ///     [Tile(1024), Tile(1024), Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ]);
+    /// assert!(!board.ever_won());
+    ///
+    /// board.r#move(MoveDirection::Left);
+    /// board.clear_row(0, false);
+    ///
+    /// assert!(!board.has_winning_tile());
+    /// assert!(board.ever_won());
+    /// ```
+    pub fn ever_won(&self) -> bool {
+        self.ever_won
+    }
+
+    pub fn has_lost(&self) -> bool {
+        !self.can_move()
+    }
+
+    /// Checks if moving in `dir` could lose the game, because some spawn
+    /// after the move would leave no moves to make
+    ///
+    /// Every spawn the rules allow is tried: each tile value in the
+    /// [`spawn_distribution`](GameConfig::spawn_distribution) in each space
+    /// the [`spawn_policy`](GameConfig::spawn_policy) picks from. The move is
+    /// made like a real one first, so tiles [decay](GameConfig::decay)
+    /// before anything spawns, and a [combo](GameConfig::combo) only spawns
+    /// its bonus tiles. A move that isn't [legal](Self::is_legal_move)
+    /// doesn't spawn anything, so it never risks losing.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection};
+    ///
+    /// let board = GameBoard::from([
+    ///     [Tile(2), Tile(4), Tile(8), Tile(16)],
+    ///     [Tile(4), Tile(8), Tile(16), Tile(32)],
+    ///     [Tile(8), Tile(16), Tile(32), Tile(4)],
+    ///     [Vacant, Tile(32), Tile(64), Tile(128)],
+    /// ]);
+    ///
+    /// // a 2 spawning in the bottom right ends the game, but a 4 doesn't
+    /// assert!(board.move_risks_loss(MoveDirection::Left));
+    /// assert!(!board.move_guarantees_loss(MoveDirection::Left));
+    /// ```
+    pub fn move_risks_loss(&self, dir: MoveDirection) -> bool {
+        self.any_outcome(dir, &GameBoard::has_lost)
+    }
+
+    /// Checks if moving in `dir` always loses the game, because every spawn
+    /// after the move would leave no moves to make
+    ///
+    /// Spawns are tried the same way as
+    /// [`move_risks_loss`](Self::move_risks_loss), and a move that isn't
+    /// [legal](Self::is_legal_move) never loses.
+    pub fn move_guarantees_loss(&self, dir: MoveDirection) -> bool {
+        self.is_legal_move(dir) && !self.any_outcome(dir, &|b| !b.has_lost())
+    }
+
+    /// Checks if `pred` holds for the board after moving in `dir` with any of
+    /// the spawns that could follow, or `false` if the move isn't legal
+    fn any_outcome(
+        &self,
+        dir: MoveDirection,
+        pred: &dyn Fn(&GameBoard) -> bool,
+    ) -> bool {
+        if !self.is_legal_move(dir) {
+            return false;
+        }
+
+        let mut board = self.clone();
+        board.history.clear();
+        board.staged = None;
+
+        board.age_tiles();
+        let mut outcome = board.slide(dir);
+        let spawns = board.settle_move(&mut outcome);
+
+        board.any_spawns(spawns, pred)
+    }
+
+    /// Checks if `pred` holds for the board after any `count` spawns
+    fn any_spawns(
+        &self,
+        count: usize,
+        pred: &dyn Fn(&GameBoard) -> bool,
+    ) -> bool {
+        if count == 0 || self.empty_count() == 0 {
+            return pred(self);
+        }
+
+        self.config
+            .spawn_distribution
+            .iter()
+            .filter(|(_, chance)| *chance > 0.0)
+            .any(|(value, _)| {
+                self.spawn_candidates(*value).into_iter().any(|loc| {
+                    let mut board = self.clone();
+                    board.set(loc, BoardSpace::Tile(*value));
+                    board.any_spawns(count - 1, pred)
+                })
+            })
+    }
+
+    /// Checks if moving in `dir` would change the board
+    pub fn is_legal_move(&self, dir: MoveDirection) -> bool {
+        if !self.config.allows(dir) {
+            return false;
+        }
+
+        let mut cells = self.cells;
+        slide_cells(&mut cells, dir, &self.config).moved
+    }
+
+    /// Gets every direction that would change the board if moved in, in the
+    /// order of [`MoveDirection::all`]
+    pub fn legal_moves(&self) -> Vec<MoveDirection> {
+        MoveDirection::all()
+            .into_iter()
+            .filter(|dir| self.is_legal_move(*dir))
+            .collect()
+    }
+
+    /// Gets whether moving in each direction would change the board, indexed
+    /// by [`MoveDirection`]
+    ///
+    /// This is the same as [`legal_moves`](Self::legal_moves) without
+    /// allocating, and every entry is `false` exactly when the game
+    /// [is lost](Self::has_lost).
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection};
+    ///
+    /// let mut board = GameBoard::empty();
+    /// board.set((0, 0), Tile(2));
+    ///
+    /// let mask = board.legal_move_mask();
+    /// assert!(!mask[MoveDirection::Left as usize]);
+    /// assert!(mask[MoveDirection::Right as usize]);
+    /// ```
+    pub fn legal_move_mask(&self) -> [bool; 4] {
+        MoveDirection::all().map(|dir| self.is_legal_move(dir))
+    }
+
+    /// Gets the spaces the board would have after moving in `dir`, before
+    /// any new tiles are spawned, without changing the board
+    ///
+    /// A move that isn't [legal](Self::is_legal_move) gives the current
+    /// spaces.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection};
+    ///
+    /// let board = GameBoard::from([
+    ///     [Tile(2), Tile(2), Vacant, Tile(4)],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ]);
+    ///
+    /// let preview = board.preview_cells(MoveDirection::Left);
+    /// assert_eq!(preview[0], [Tile(4), Tile(4), Vacant, Vacant]);
+    /// assert_eq!(board.preview_cells(MoveDirection::Up), board.cells);
+    /// ```
+    pub fn preview_cells(&self, dir: MoveDirection) -> GameBoardCells {
+        let mut cells = self.cells;
+        if self.config.allows(dir) {
+            slide_cells(&mut cells, dir, &self.config);
+        }
+
+        cells
+    }
+
+    /// Gets the board each [legal move](Self::legal_moves) would leave,
+    /// before any new tiles are spawned, in the order of
+    /// [`MoveDirection::all`]
+    ///
+    /// Each board is a copy with its tiles slid and the merges scored, and
+    /// nothing else that happens at the end of a move: no tiles spawn or
+    /// [decay](GameConfig::decay), and the move count stays the same. The
+    /// copies start with the same random number generator as this board, so
+    /// moving on them doesn't change the spawns on this one.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection};
+    ///
+    /// let board = GameBoard::from([
+    ///     [Tile(2), Tile(2), Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ]);
+    ///
+    /// let successors = board.all_legal_successors();
+    /// let dirs: Vec<_> = successors.iter().map(|(dir, _)| *dir).collect();
+    /// assert_eq!(
+    ///     dirs,
+    ///     [MoveDirection::Left, MoveDirection::Right, MoveDirection::Down]
+    /// );
+    ///
+    /// let (_, left) = &successors[0];
+    /// assert_eq!(left.cells[0], [Tile(4), Vacant, Vacant, Vacant]);
+    /// assert_eq!(left.score, 4);
+    /// ```
+    pub fn all_legal_successors(&self) -> Vec<(MoveDirection, GameBoard)> {
+        self.legal_moves()
+            .into_iter()
+            .map(|dir| {
+                let mut board = self.clone();
+                board.staged = None;
+                board.last_spawns.clear();
+                board.slide(dir);
+
+                (dir, board)
+            })
+            .collect()
+    }
+
+    /// Previews moving in `dir` as a series of frames, without changing the
+    /// board
+    ///
+    /// Each frame moves every sliding tile one space further, until they've
+    /// all stopped. If any tiles merge, one more frame shows the merged tiles.
+    /// Tiles that merge around the edge of a [wrapping](GameConfig::wrap)
+    /// board stay where they are until the merge frame.
+    ///
+    /// The last frame is the board as [`move`](Self::move) would leave it
+    /// _before_ spawning new tiles. Spawns are random, so they aren't
+    /// previewed; once the move is made, its [`cells`](Self::cells) are the
+    /// frame with the new tiles. No frames are returned for a move that
+    /// wouldn't change the board.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection};
+    ///
+    /// let board = GameBoard::from([
+    ///     [Vacant, Tile(2), Vacant, Tile(2)],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    /// ]);
+    ///
+    /// let rows: Vec<_> = board
+    ///     .animation_frames(MoveDirection::Left)
+    ///     .iter()
+    ///     .map(|frame| frame[0])
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     rows,
+    ///     [
+    ///         [Tile(2), Vacant, Tile(2), Vacant],
+    ///         [Tile(2), Tile(2), Vacant, Vacant],
+    ///         [Tile(2), Vacant, Vacant, Vacant],
+    ///         [Tile(4), Vacant, Vacant, Vacant],
+    ///     ]
+    /// );
+    /// ```
+    pub fn animation_frames(&self, dir: MoveDirection) -> Vec<GameBoardCells> {
+        if !self.config.allows(dir) {
+            return Vec::new();
+        }
+
+        let mut steps = 0;
+        let mut cells = self.cells;
+        for_each_line(&mut cells, dir, |row| {
+            for (from, to, _) in slide_plan(row, &self.config) {
+                steps = steps.max(from - to);
+            }
+        });
+
+        let mut frames = Vec::with_capacity(steps + 1);
+        for step in 1..=steps {
+            let mut frame = self.cells;
+            for_each_line(&mut frame, dir, |row| {
+                let plan = slide_plan(row, &self.config);
+
+                *row = [BoardSpace::Vacant; GAME_BOARD_SIZE];
+                for (from, to, value) in plan {
+                    row[from - step.min(from - to)] = BoardSpace::Tile(value);
+                }
+            });
+
+            frames.push(frame);
+        }
+
+        let mut merged = self.cells;
+        slide_cells(&mut merged, dir, &self.config);
+        if merged != *frames.last().unwrap_or(&self.cells) {
+            frames.push(merged);
+        }
+
+        frames
+    }
+
+    /// Summarizes the current state of the board
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard, MoveDirection};
+    ///
+    /// let board = GameBoard::from([
+    ///     [Tile(2), Tile(4), Tile(2), Tile(4)],
+    ///     [Tile(4), Tile(2), Tile(4), Tile(2)],
+    ///     [Tile(2), Tile(4), Tile(2), Tile(4)],
+    ///     [Tile(4), Tile(2), Tile(4), Vacant],
+    /// ]);
+    ///
+    /// let summary = board.describe();
+    /// assert_eq!(summary.highest_tile, Some(4));
+    /// assert_eq!(summary.empty_count, 1);
+    /// assert!(!summary.is_lost);
+    /// assert_eq!(
+    ///     summary.legal_moves,
+    ///     vec![MoveDirection::Right, MoveDirection::Down]
+    /// );
+    /// ```
+    pub fn describe(&self) -> BoardSummary {
+        let mut highest_tile = None;
+        let mut empty_count = 0;
+
+        for space in self.cells.iter().flatten() {
+            match space {
+                BoardSpace::Tile(t) => {
+                    highest_tile = highest_tile.max(Some(*t));
+                },
+                BoardSpace::Vacant => empty_count += 1,
+            }
+        }
+
+        let legal_moves = self.legal_moves();
+
+        BoardSummary {
+            highest_tile,
+            empty_count,
+            score: self.score,
+            moves: self.moves,
+            is_won: highest_tile.is_some_and(|t| t >= self.config.win_target),
+            is_lost: legal_moves.is_empty(),
+            legal_moves,
+        }
+    }
+
+    fn can_move(&self) -> bool {
+        cells_can_move(&self.cells, &self.config)
+    }
+
+    /// Flips the board over its top-left to bottom-right diagonal, swapping
+    /// the rows and columns
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard};
+    ///
+    /// let mut board = GameBoard::from([
+    ///     [Tile(2), Tile(4), Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Tile(8)],
+    /// ]);
+    ///
+    /// board.transpose_in_place();
+    /// assert_eq!(board.get((0, 1)), Tile(4));
+    /// assert_eq!(board.get((3, 3)), Tile(8));
+    /// ```
+    pub fn transpose_in_place(&mut self) {
+        transpose(&mut self.cells);
+        transpose(&mut self.ages);
+    }
+
+    /// Draws the board as a grid of boxes, for showing it in monospaced text
+    ///
+    /// Every column is as wide as the widest tile on the board.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard};
+    ///
+    /// let board = GameBoard::from([
+    ///     [Tile(2), Vacant, Vacant, Vacant],
+    ///     [Vacant, Tile(16), Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Tile(128)],
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     board.to_ascii_art(),
+    ///     "\
+    /// ┌─────┬─────┬─────┬─────┐
+    /// │  2  │     │     │     │
+    /// ├─────┼─────┼─────┼─────┤
+    /// │     │ 16  │     │     │
+    /// ├─────┼─────┼─────┼─────┤
+    /// │     │     │     │     │
+    /// ├─────┼─────┼─────┼─────┤
+    /// │     │     │     │ 128 │
+    /// └─────┴─────┴─────┴─────┘
+    /// "
+    /// );
+    /// ```
+    pub fn to_ascii_art(&self) -> String {
+        let width = self
+            .cells
+            .iter()
+            .flatten()
+            .map(|space| space.to_string().len())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        let line = |left: &str, middle: &str, right: &str| {
+            let segment = "─".repeat(width + 2);
+            let segments = vec![segment; self.cells.len()];
+
+            format!("{left}{}{right}\n", segments.join(middle))
+        };
+
+        let mut art = line("┌", "┬", "┐");
+
+        for (y, row) in self.cells.iter().enumerate() {
+            if y > 0 {
+                art += &line("├", "┼", "┤");
+            }
+
+            for space in row {
+                art += &format!("│ {:^width$} ", space.to_string());
+            }
+
+            art += "│\n";
+        }
+
+        art += &line("└", "┴", "┘");
+
+        art
+    }
+
+    /// Writes the board as a compact string that only depends on its cells,
+    /// score, and move count, for comparing against in snapshot tests
+    ///
+    /// Each row of the board is a line, with every tile written as the power
+    /// of two it is (so `1` is `2`, and `11` is `2048`) and vacant spaces as
+    /// `.`. Tiles that aren't a power of two are written as their value in
+    /// brackets. Every space is right-aligned to two columns. A last line
+    /// holds the score and move count. The format won't change between
+    /// versions, since changing it would break every existing snapshot.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard};
+    ///
+    /// let board = GameBoard::from([
+    ///     [Tile(2), Vacant, Vacant, Vacant],
+    ///     [Vacant, Tile(16), Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Tile(3)],
+    ///     [Vacant, Vacant, Vacant, Tile(2048)],
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     board.snapshot(),
+    ///     concat!(
+    ///         " 1  .  .  .\n",
+    ///         " .  4  .  .\n",
+    ///         " .  .  . [3]\n",
+    ///         " .  .  . 11\n",
+    ///         "score 0 moves 0\n",
+    ///     )
+    /// );
+    /// ```
+    pub fn snapshot(&self) -> String {
+        let mut snapshot = String::new();
+
+        for row in &self.cells {
+            let spaces: Vec<_> = row
+                .iter()
+                .map(|space| match space {
+                    BoardSpace::Vacant => format!("{:>2}", "."),
+                    BoardSpace::Tile(t) if t.is_power_of_two() => {
+                        format!("{:>2}", t.trailing_zeros())
+                    },
+                    BoardSpace::Tile(t) => format!("[{t}]"),
+                })
+                .collect();
+
+            snapshot += &spaces.join(" ");
+            snapshot += "\n";
+        }
+
+        snapshot += &format!("score {} moves {}\n", self.score, self.moves);
+        snapshot
+    }
+
+    /// Gets the location of every vacant space on the board
+    ///
+    /// Locations are returned in row-major order: rows from top to bottom,
+    /// and within each row from left to right. Spawning picks from this list,
+    /// so the order must stay stable for seeded games to replay identically.
+    fn all_empty_spaces(&self) -> Vec<GameBoardLocation> {
+        let mut locations: Vec<GameBoardLocation> = vec![];
+
+        for y in 0..self.cells.len() {
+            for x in 0..self.cells.len() {
+                if let BoardSpace::Vacant = self.cells[y][x] {
+                    locations.push((x, y));
+                }
+            }
+        }
+
+        locations
+    }
+
+    /// Spawns up to `count` tiles, each into a different vacant space
+    fn add_random_tiles(&mut self, count: usize) {
+        // every spawn fills a vacant space, so picking from the remaining
+        // vacant spaces each time keeps the spawns apart
+        for _ in 0..count {
+            if !self.add_queued_tile() {
+                self.add_random_tile();
+            }
+        }
+    }
+
+    /// Spawns the next tile from the [spawn queue](Self::set_spawn_queue),
+    /// returning `false` if the queue is empty
+    ///
+    /// The queued tile is kept for a later spawn if the board is full.
+    fn add_queued_tile(&mut self) -> bool {
+        let (pos, value) = match self.spawn_queue.front() {
+            Some(spawn) => *spawn,
+            None => return false,
+        };
+
+        let free_spaces = self.all_empty_spaces();
+        let first_free = match free_spaces.first() {
+            Some(loc) => *loc,
+            None => return true,
+        };
+
+        let pos = pos
+            .filter(|loc| free_spaces.contains(loc))
+            .unwrap_or(first_free);
+
+        self.spawn_queue.pop_front();
+        self.set(pos, BoardSpace::Tile(value));
+        true
+    }
+
+    /// Gets the vacant spaces a new tile worth `value` may spawn in,
+    /// following the configured [`spawn_policy`](GameConfig::spawn_policy)
+    fn spawn_candidates(&self, value: usize) -> Vec<GameBoardLocation> {
+        let free_spaces = self.all_empty_spaces();
+
+        match self.config.spawn_policy {
+            SpawnPolicy::Uniform => free_spaces,
+            SpawnPolicy::KeepAlive => {
+                let alive: Vec<_> = free_spaces
+                    .iter()
+                    .copied()
+                    .filter(|(x, y)| {
+                        let mut cells = self.cells;
+                        cells[*y][*x] = BoardSpace::Tile(value);
+                        cells_can_move(&cells, &self.config)
+                    })
+                    .collect();
+
+                if alive.is_empty() {
+                    free_spaces
+                } else {
+                    alive
+                }
+            },
+            SpawnPolicy::AvoidHighestTile => {
+                let highest = match self.highest_tile() {
+                    Some(t) => BoardSpace::Tile(t),
+                    None => return free_spaces,
+                };
+
+                let away: Vec<_> = free_spaces
+                    .iter()
+                    .copied()
+                    .filter(|loc| {
+                        self.neighbors(*loc).all(|(_, space)| space != highest)
+                    })
+                    .collect();
+
+                if away.is_empty() {
+                    free_spaces
+                } else {
+                    away
+                }
+            },
+        }
+    }
+
+    /// Picks the value of a spawned tile from the
+    /// [`spawn_distribution`](Self::spawn_distribution)
+    fn random_spawn_value(&mut self) -> usize {
+        let mut roll: f64 = self.rng.gen();
+
+        for (value, chance) in &self.config.spawn_distribution {
+            if roll < *chance {
+                return *value;
+            }
+
+            roll -= chance;
+        }
+
+        // rounding can leave a sliver past the last chance
+        self.config.spawn_distribution.last().unwrap().0
+    }
+
+    fn add_random_tile(&mut self) {
+        if self.empty_count() > 0 {
+            let value = self.random_spawn_value();
+            let free_spaces = self.spawn_candidates(value);
+            let tile = BoardSpace::Tile(value);
+
+            let pos = free_spaces
+                .choose(&mut self.rng)
+                .expect("failed to choose random position");
+
+            self.set(*pos, tile);
+        }
+    }
+}
+
+impl Default for GameBoard {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl From<GameBoardCells> for GameBoard {
+    fn from(def: GameBoardCells) -> Self {
+        Self::with_cells_and_config(GameConfig::default(), Some(def), 0)
+    }
+}
+
+/// The error returned by [`GameBoard::undo`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoError {
+    /// No move has changed the board since it was created or last undone
+    /// back to the start
+    NothingToUndo,
+    /// Every one of the game's [`undos`](GameConfig::undos) has been used
+    NoUndosLeft,
+}
+
+impl fmt::Display for UndoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NothingToUndo => write!(f, "there's no move to undo"),
+            Self::NoUndosLeft => write!(f, "no undos are left"),
+        }
+    }
+}
+
+impl Error for UndoError {}
+
+/// The error returned by [`GameBoard::with_handicap`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandicapError {
+    /// The tile at `index` isn't on the board
+    OutOfBounds { index: usize },
+    /// The tile at `index` is on the same space as an earlier one
+    Overlapping { index: usize },
+    /// The tile at `index` has a value of 0
+    InvalidTile { index: usize },
+    /// No move can be made, because there are no tiles or they fill the
+    /// board without any next to each other that merge, so the game would
+    /// be over before it started
+    NotPlayable,
+}
+
+impl fmt::Display for HandicapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { index } => {
+                write!(f, "tile {index} isn't on the board")
+            },
+            Self::Overlapping { index } => {
+                write!(f, "tile {index} is on the same space as another tile")
+            },
+            Self::InvalidTile { index } => write!(f, "tile {index} is 0"),
+            Self::NotPlayable => write!(f, "no move can be made on the board"),
+        }
+    }
+}
+
+impl Error for HandicapError {}
+
+/// The error returned by [`try_move`](GameBoard::try_move) for a move that
+/// can't be made
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IllegalMove;
+
+impl fmt::Display for IllegalMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "that move can't be made")
+    }
+}
+
+impl Error for IllegalMove {}
+
+/// The error returned by [`GameBoard::move_with_spawn`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnError {
+    /// The move wouldn't change the board, or the game is over, so nothing
+    /// would spawn
+    IllegalMove,
+    /// The space isn't on the board, or has a tile in it after the move
+    NotVacant,
+}
+
+impl fmt::Display for SpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IllegalMove => IllegalMove.fmt(f),
+            Self::NotVacant => {
+                write!(f, "a tile can't spawn there after that move")
+            },
+        }
+    }
+}
+
+impl Error for SpawnError {}
+
+/// The error returned by [`GameBoard::try_merge_cells`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeError {
+    /// One of the spaces isn't on the board
+    OutOfBounds,
+    /// The spaces aren't next to each other, so their tiles could never meet
+    NotAdjacent,
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds => write!(f, "that space isn't on the board"),
+            Self::NotAdjacent => {
+                write!(f, "those spaces aren't next to each other")
+            },
+        }
+    }
+}
+
+impl Error for MergeError {}
+
+/// The error returned by [`GameBoard::replay_with_spawns`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The spawns ran out before the starting tiles were placed, or before
+    /// the move after the first `moves_replayed` moves, which changed the
+    /// board
+    TooFewSpawns { found: usize, moves_replayed: usize },
+    /// Every move was replayed with spawns left over
+    TooManySpawns { used: usize, found: usize },
+    /// The spawn at `index` is off the board, or on a space that already
+    /// has a tile
+    InvalidSpawn { index: usize },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFewSpawns {
+                found,
+                moves_replayed,
+            } => write!(
+                f,
+                "ran out of spawns after {moves_replayed} moves, with only \
+                 {found} given"
+            ),
+            Self::TooManySpawns { used, found } => write!(
+                f,
+                "only {used} of the {found} spawns were used by the moves"
+            ),
+            Self::InvalidSpawn { index } => {
+                write!(f, "spawn {index} isn't on a vacant space on the board")
+            },
+        }
+    }
+}
+
+impl Error for ReplayError {}
+
+/// The result of a [`move`](GameBoard::move)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MoveOutcome {
+    /// Whether any tile moved or merged
+    pub moved: bool,
+    /// The points scored by merges during the move
+    pub score_delta: usize,
+    /// The points scored on top of [`score_delta`](Self::score_delta), from
+    /// the [`per_move_score`](GameConfig::per_move_score),
+    /// [`spawn_score`](GameConfig::spawn_score), and
+    /// [combo](GameConfig::combo) multiplier
+    pub extra_score: usize,
+    /// The number of merges made during the move
+    pub merges: usize,
+    /// The value of the tile made by each merge, in the order they were made
+    pub merged_values: Vec<usize>,
+    /// The most merges made in any single row or column
+    ///
+    /// A move that merges `2 2 4 4` into `4 8` makes two merges in one line.
+    pub max_line_merges: usize,
+    /// The number of extra tiles earned by merges of at least the
+    /// [`bonus_threshold`](GameConfig::bonus_threshold)
+    pub bonus_spawns: usize,
+    /// The number of tiles that [decayed](GameConfig::decay) at the end of
+    /// the move
+    pub decayed: usize,
+    /// Whether this move was a [combo](GameConfig::combo), so only bonus
+    /// tiles spawned after it
+    pub combo: bool,
+    /// Whether this move ended the game, by winning or losing it
+    ///
+    /// This is only ever `true` for a single move in a game.
+    pub just_ended: bool,
+    /// Whether this move reached the [win target](GameConfig::win_target)
+    /// for the first time, with [`WinBehavior::Prompt`]
+    pub just_won: bool,
+}
+
+impl MoveOutcome {
+    /// Adds the results of a following slide that's part of the same move
+    fn absorb(&mut self, other: MoveOutcome) {
+        self.moved |= other.moved;
+        self.score_delta += other.score_delta;
+        self.merges += other.merges;
+        self.merged_values.extend(other.merged_values);
+        self.max_line_merges = self.max_line_merges.max(other.max_line_merges);
+    }
+
+    /// Records a merge that made a tile of `new_val`
+    fn record_merge(&mut self, new_val: usize) {
+        self.score_delta += new_val;
+        self.merges += 1;
+        self.merged_values.push(new_val);
+    }
+}
+
+/// A snapshot of the state of a [`GameBoard`], from
+/// [`describe`](GameBoard::describe)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardSummary {
+    /// The value of the highest tile, or `None` if the board is empty
+    pub highest_tile: Option<usize>,
+    /// The number of vacant spaces
+    pub empty_count: usize,
+    pub score: usize,
+    /// The number of moves made that changed the board
+    pub moves: usize,
+    pub is_won: bool,
+    pub is_lost: bool,
+    /// Every direction that would change the board if moved in
+    pub legal_moves: Vec<MoveDirection>,
+}
+
+/// The direction of a [`move`](GameBoard::move)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MoveDirection {
+    Left = 0,
+    Up = 1,
+    Right = 2,
+    Down = 3,
+}
+
+impl MoveDirection {
+    /// Gets every direction, ordered by discriminant
+    pub fn all() -> [Self; 4] {
+        [Self::Left, Self::Up, Self::Right, Self::Down]
+    }
+
+    /// Gets the `(dx, dy)` step one space in this direction, where `x` grows
+    /// to the right and `y` grows downward, like a
+    /// [`GameBoardLocation`]
+    ///
+    /// ```
+    /// use lib_2048::MoveDirection;
+    ///
+    /// assert_eq!(MoveDirection::Left.as_vector(), (-1, 0));
+    /// assert_eq!(MoveDirection::Down.as_vector(), (0, 1));
+    /// ```
+    pub fn as_vector(self) -> (isize, isize) {
+        match self {
+            Self::Left => (-1, 0),
+            Self::Up => (0, -1),
+            Self::Right => (1, 0),
+            Self::Down => (0, 1),
+        }
+    }
+}
+
+impl FromStr for MoveDirection {
+    type Err = ParseMoveError;
+
+    /// Parses a direction from its name or first letter, ignoring case
+    ///
+    /// ```
+    /// use lib_2048::MoveDirection;
+    ///
+    /// assert_eq!("up".parse(), Ok(MoveDirection::Up));
+    /// assert_eq!("L".parse(), Ok(MoveDirection::Left));
+    /// assert!("sideways".parse::<MoveDirection>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "l" | "left" => Ok(Self::Left),
+            "u" | "up" => Ok(Self::Up),
+            "r" | "right" => Ok(Self::Right),
+            "d" | "down" => Ok(Self::Down),
+            _ => Err(ParseMoveError {
+                position: 0,
+                found: s.to_string(),
+            }),
+        }
+    }
+}
+
+/// The error returned when text can't be parsed as moves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMoveError {
+    /// The index of the character the unknown move starts at
+    pub position: usize,
+    /// The text that isn't a move
+    pub found: String,
+}
+
+impl fmt::Display for ParseMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown move `{}` at position {}",
+            self.found, self.position
+        )
+    }
+}
+
+impl Error for ParseMoveError {}
+
+/// A corner of the board, for [`move_to_corner`](GameBoard::move_to_corner)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Corner {
+    /// Gets the two moves that slide toward this corner, vertical first
+    pub fn directions(self) -> [MoveDirection; 2] {
+        match self {
+            Self::TopLeft => [MoveDirection::Up, MoveDirection::Left],
+            Self::TopRight => [MoveDirection::Up, MoveDirection::Right],
+            Self::BottomLeft => [MoveDirection::Down, MoveDirection::Left],
+            Self::BottomRight => [MoveDirection::Down, MoveDirection::Right],
+        }
+    }
+}
+
+/// A space on the [`GameBoard`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BoardSpace {
+    /// An empty space
+    Vacant,
+    /// A tile with a numeric value
+    Tile(usize),
+}
+
+impl BoardSpace {
+    /// Gets the numeric value of the tile in this space, or `None` if it's
+    /// vacant
+    pub fn value(&self) -> Option<usize> {
+        match self {
+            Self::Vacant => None,
+            Self::Tile(t) => Some(*t),
+        }
+    }
+}
+
+impl fmt::Display for BoardSpace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Vacant => write!(f, ""),
+            Self::Tile(n) => write!(f, "{n}"),
+        }
+    }
+}
+
+/// Formats a score for display, grouping its digits in threes with
+/// `separator` if one is given
+///
+/// Only use this for text shown to players; tile values should stay plain
+/// numbers so they can be parsed back.
+///
+/// ```
+/// use lib_2048::format_score;
+///
+/// assert_eq!(format_score(131072, Some(',')), "131,072");
+/// assert_eq!(format_score(131072, None), "131072");
+/// ```
+pub fn format_score(score: usize, separator: Option<char>) -> String {
+    let digits = score.to_string();
+
+    let separator = match separator {
+        Some(separator) => separator,
+        None => return digits,
+    };
+
+    let mut formatted = String::with_capacity(digits.len() * 2);
+
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            formatted.push(separator);
+        }
+
+        formatted.push(digit);
+    }
+
+    formatted
+}
+
+/// Slides and merges every tile in `cells` in `dir`, following the rules in
+/// `config`
+fn slide_cells(
+    cells: &mut GameBoardCells,
+    dir: MoveDirection,
+    config: &GameConfig,
+) -> MoveOutcome {
+    let mut outcome = MoveOutcome::default();
+
+    for_each_line(cells, dir, |row| {
+        let merges = outcome.merges;
+        slide_row(row, config, &mut outcome);
+
+        outcome.max_line_merges =
+            outcome.max_line_merges.max(outcome.merges - merges);
+    });
+
+    outcome
+}
+
+/// Checks if any move allowed by `config` would change `cells`
+fn cells_can_move(cells: &GameBoardCells, config: &GameConfig) -> bool {
+    if config.allowed_directions != [true; 4] {
+        return MoveDirection::all().into_iter().any(|dir| {
+            let mut cells = *cells;
+            config.allows(dir) && slide_cells(&mut cells, dir, config).moved
+        });
+    }
+
+    // an empty board has nothing to slide, and any other board with a vacant
+    // space can slide some tile into it
+    if cells
+        .iter()
+        .flatten()
+        .all(|space| *space == BoardSpace::Vacant)
+    {
+        return false;
+    }
+
+    for (y, row) in cells.iter().enumerate() {
+        for (x, space) in row.iter().enumerate() {
+            if let BoardSpace::Vacant = space {
+                return true;
+            }
+
+            // checking right and down covers every adjacent pair once
+            let right = row.get(x + 1);
+            let below = cells.get(y + 1).map(|row| &row[x]);
+
+            if [right, below]
+                .into_iter()
+                .flatten()
+                .any(|other| spaces_merge(*space, *other, config))
+            {
+                return true;
+            }
+        }
+    }
+
+    // a full board can still merge the tiles at either end of a line when it
+    // wraps around
+    if config.wrap {
+        let last = cells.len() - 1;
+
+        let rows_wrap = cells
+            .iter()
+            .any(|row| spaces_merge(row[0], row[last], config));
+        let columns_wrap = (0..cells.len())
+            .any(|x| spaces_merge(cells[0][x], cells[last][x], config));
+
+        if rows_wrap || columns_wrap {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Checks if two spaces hold tiles that merge when they meet
+fn spaces_merge(a: BoardSpace, b: BoardSpace, config: &GameConfig) -> bool {
+    match (a, b) {
+        (BoardSpace::Tile(a), BoardSpace::Tile(b)) => config.can_merge(a, b),
+        _ => false,
+    }
+}
+
+/// Checks that `(x, y)` is on the board and has no tile in `cells`
+fn is_vacant(cells: &GameBoardCells, (x, y): GameBoardLocation) -> bool {
+    cells.get(y).and_then(|row| row.get(x)) == Some(&BoardSpace::Vacant)
+}
+
+/// Checks that a spawn distribution can be
+/// [normalized](normalize_distribution) without panicking, for loaders that
+/// should reject it instead
+pub(crate) fn valid_distribution(distribution: &[(usize, f64)]) -> bool {
+    let total: f64 = distribution.iter().map(|(_, chance)| chance).sum();
+    let negative = distribution
+        .iter()
+        .any(|(_, chance)| chance.is_nan() || *chance < 0.0);
+
+    !negative && total.is_finite() && total > 0.0
+}
+
+/// Scales the chances in a spawn distribution so they add up to `1.0`
+fn normalize_distribution(
+    distribution: Vec<(usize, f64)>,
+) -> Vec<(usize, f64)> {
+    assert!(
+        distribution.iter().all(|(_, chance)| *chance >= 0.0),
+        "spawn chances can't be negative"
+    );
+
+    let total: f64 = distribution.iter().map(|(_, chance)| chance).sum();
+    assert!(total > 0.0, "some tile must have a chance to spawn");
+
+    distribution
+        .into_iter()
+        .map(|(value, chance)| (value, chance / total))
+        .collect()
+}
+
+/// Calls `f` with every line in `cells` running in `dir`, each ordered so
+/// that its start is the edge being moved toward
+fn for_each_line<T: Copy>(
+    cells: &mut [[T; GAME_BOARD_SIZE]; GAME_BOARD_SIZE],
+    dir: MoveDirection,
+    mut f: impl FnMut(&mut [T; GAME_BOARD_SIZE]),
+) {
+    // columns are handled as rows of the transposed board, and moves toward
+    // the right or bottom as reversed rows
+    let vertical = matches!(dir, MoveDirection::Up | MoveDirection::Down);
+    let reversed = matches!(dir, MoveDirection::Right | MoveDirection::Down);
+
+    if vertical {
+        transpose(cells);
+    }
+
+    for row in cells.iter_mut() {
+        if reversed {
+            row.reverse();
+        }
+
+        f(row);
+
+        if reversed {
+            row.reverse();
+        }
+    }
+
+    if vertical {
+        transpose(cells);
+    }
+}
+
+/// Slides and merges every tile in `row` toward its start, recording what
+/// happened in `outcome`
+///
+/// If [`wrap`](GameConfig::wrap) is set, the row is treated as a ring: after
+/// the usual merges, the last tile in the row merges into the first one if
+/// they're equal and neither has already merged. Since no tile merges twice
+/// in one move, a row like `2 2 2 2` still becomes `4 4 _ _`.
+fn slide_row(
+    row: &mut [BoardSpace; GAME_BOARD_SIZE],
+    config: &GameConfig,
+    outcome: &mut MoveOutcome,
+) {
+    let mut merged = [false; GAME_BOARD_SIZE];
+
+    for x in 0..row.len() {
+        if let BoardSpace::Tile(t) = row[x] {
+            for x2 in (x + 1)..row.len() {
+                match row[x2] {
+                    BoardSpace::Tile(t2) if config.can_merge(t, t2) => {
+                        let new_val = t * 2;
+                        outcome.record_merge(new_val);
+
+                        row[x] = BoardSpace::Tile(new_val);
+                        row[x2] = BoardSpace::Vacant;
+                        merged[x] = true;
+
+                        outcome.moved = true;
+                        break;
+                    },
+
+                    BoardSpace::Tile(_) => break,
+                    _ => continue,
+                }
+            }
+        }
+    }
+
+    if config.wrap {
+        let first = row.iter().position(|s| *s != BoardSpace::Vacant);
+        let last = row.iter().rposition(|s| *s != BoardSpace::Vacant);
+
+        if let (Some(first), Some(last)) = (first, last) {
+            if let (BoardSpace::Tile(t), BoardSpace::Tile(t2)) =
+                (row[first], row[last])
+            {
+                if first != last
+                    && !merged[first]
+                    && !merged[last]
+                    && config.can_merge(t, t2)
+                {
+                    let new_val = t * 2;
+                    outcome.record_merge(new_val);
+
+                    row[first] = BoardSpace::Tile(new_val);
+                    row[last] = BoardSpace::Vacant;
+
+                    outcome.moved = true;
+                }
+            }
+        }
+    }
+
+    outcome.moved |= compact_row(row);
+}
+
+/// Works out where each tile in `row` ends up when it's slid toward its
+/// start, the same way as [`slide_row`], as `(from, to, value)`
+///
+/// Tiles are listed in the order they appear in the row. A tile that merges
+/// around the edge of a wrapping row stays where it is.
+fn slide_plan(
+    row: &[BoardSpace; GAME_BOARD_SIZE],
+    config: &GameConfig,
+) -> Vec<(usize, usize, usize)> {
+    let tiles: Vec<_> = row
+        .iter()
+        .enumerate()
+        .filter_map(|(x, space)| space.value().map(|value| (x, value)))
+        .collect();
+
+    // neighboring equal tiles pair up from the start of the row, and each
+    // pair or leftover tile slides into the next free space
+    let mut plan = Vec::with_capacity(tiles.len());
+    let mut singles = Vec::new();
+    let mut to = 0;
+    let mut i = 0;
+    while i < tiles.len() {
+        let (from, value) = tiles[i];
+        plan.push((from, to, value));
+
+        if tiles
+            .get(i + 1)
+            .is_some_and(|next| config.can_merge(value, next.1))
+        {
+            plan.push((tiles[i + 1].0, to, value));
+            i += 2;
+        } else {
+            singles.push(to);
+            i += 1;
+        }
+
+        to += 1;
+    }
+
+    // only a leftover tile at each end can merge around the edge
+    if config.wrap
+        && to > 1
+        && singles.first() == Some(&0)
+        && singles.last() == Some(&(to - 1))
+    {
+        let last = plan.len() - 1;
+        if config.can_merge(plan[0].2, plan[last].2) {
+            plan[last].1 = plan[last].0;
+        }
+    }
+
+    plan
+}
+
+/// Slides every tile in `row` toward its start without merging, returning
+/// whether any tile moved
+fn compact_row(row: &mut [BoardSpace; GAME_BOARD_SIZE]) -> bool {
+    let mut moved = false;
+
+    for x in 0..row.len() {
+        if let BoardSpace::Vacant = row[x] {
+            for x2 in x..row.len() {
+                if let BoardSpace::Tile(_) = row[x2] {
+                    row.swap(x, x2);
+
+                    moved = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    moved
+}
+
+fn transpose<T: Copy>(arrs: &mut [[T; GAME_BOARD_SIZE]; GAME_BOARD_SIZE]) {
+    let original = *arrs;
+
+    for (y, row) in arrs.iter_mut().enumerate() {
+        for (x, space) in row.iter_mut().enumerate() {
+            *space = original[x][y];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use rand::RngCore;
+
+    use super::{BoardSpace::*, *};
+
+    #[derive(Debug, Default)]
+    struct MockClock(AtomicU64);
+
+    impl MockClock {
+        fn advance(&self, millis: u64) {
+            self.0.fetch_add(millis, Ordering::SeqCst);
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> Duration {
+            Duration::from_millis(self.0.load(Ordering::SeqCst))
+        }
+    }
+
+    const SAMPLE_GAME_BOARD: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] = [
+        [Tile(2), Tile(2), Tile(2), Tile(2)],
+        [Tile(2), Tile(8), Tile(1), Vacant],
+        [Vacant, Vacant, Vacant, Vacant],
+        [Tile(2), Tile(4), Tile(1), Tile(2)],
+    ];
+
+    /// A full board with no tiles next to each other that merge
+    const LOST_GAME_BOARD: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] = [
+        [Tile(2), Tile(4), Tile(2), Tile(4)],
+        [Tile(4), Tile(2), Tile(4), Tile(2)],
+        [Tile(2), Tile(4), Tile(2), Tile(4)],
+        [Tile(4), Tile(2), Tile(4), Tile(2)],
+    ];
+
+    /// Makes a board from `cells` that never spawns tiles after a move, so
+    /// moves can be checked against exact boards
+    fn no_spawns(cells: GameBoardCells) -> GameBoard {
+        let config = GameConfig {
+            spawn_per_move: 0,
+            ..Default::default()
+        };
+
+        GameBoard::with_cells_and_config(config, Some(cells), 0)
+    }
+
+    #[test]
+    fn move_up() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+
+        board.r#move(MoveDirection::Up);
+
+        const EXPECTED: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] = [
+            [Tile(4), Tile(2), Tile(2), Tile(4)],
+            [Tile(2), Tile(8), Tile(2), Vacant],
+            [Vacant, Tile(4), Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+        ];
+
+        assert_eq!(board.cells, EXPECTED);
+    }
+
+    #[test]
+    fn move_down() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+
+        board.r#move(MoveDirection::Down);
+
+        const EXPECTED: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] = [
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Tile(2), Vacant, Vacant],
+            [Tile(2), Tile(8), Tile(2), Vacant],
+            [Tile(4), Tile(4), Tile(2), Tile(4)],
+        ];
+
+        assert_eq!(board.cells, EXPECTED);
+    }
+
+    #[test]
+    fn move_left() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+
+        board.r#move(MoveDirection::Left);
+
+        const EXPECTED: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] = [
+            [Tile(4), Tile(4), Vacant, Vacant],
+            [Tile(2), Tile(8), Tile(1), Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Tile(2), Tile(4), Tile(1), Tile(2)],
+        ];
+
+        assert_eq!(board.cells, EXPECTED);
+    }
+
+    #[test]
+    fn move_right() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+
+        board.r#move(MoveDirection::Right);
+
+        const EXPECTED: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] = [
+            [Vacant, Vacant, Tile(4), Tile(4)],
+            [Vacant, Tile(2), Tile(8), Tile(1)],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Tile(2), Tile(4), Tile(1), Tile(2)],
+        ];
+
+        assert_eq!(board.cells, EXPECTED);
+    }
+
+    #[test]
+    fn snapshot() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+        board.r#move(MoveDirection::Left);
+
+        assert_eq!(
+            board.snapshot(),
+            concat!(
+                " 2  2  .  .\n",
+                " 1  3  0  .\n",
+                " .  .  .  .\n",
+                " 1  2  0  1\n",
+                "score 8 moves 1\n",
+            )
+        );
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn to_svg() {
+        let mut board = no_spawns([[Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE]);
+        board.set((0, 0), Tile(2));
+        board.set((3, 3), Tile(131072));
+
+        let svg = board.to_svg();
+        let size = 4 * render::TILE_SIZE + 5 * render::GAP;
+        assert!(svg.starts_with(&format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\""
+        )));
+        assert!(svg.ends_with("</svg>"));
+
+        // the board, and one rect per space
+        assert_eq!(svg.matches("<rect").count(), 17);
+        assert_eq!(svg.matches("fill=\"#cdc1b4\"").count(), 14);
+        assert_eq!(svg.matches("fill=\"#eee4da\"").count(), 1);
+        assert_eq!(svg.matches("fill=\"#3c3a32\"").count(), 1);
+
+        assert_eq!(svg.matches("text-anchor=\"middle\"").count(), 2);
+        assert!(svg.contains("font-size=\"50\" fill=\"#776e65\""));
+        assert!(svg.contains("font-size=\"22\" fill=\"#f9f6f2\""));
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn to_rgba() {
+        let mut board = no_spawns([[Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE]);
+        board.set((0, 0), Tile(2));
+
+        let size = board.image_size();
+        let pixels = board.to_rgba();
+        assert_eq!(pixels.len(), size * size * 4);
+
+        let pixel = |x: usize, y: usize| {
+            let i = (y * size + x) * 4;
+            &pixels[i..i + 4]
+        };
+        let center = render::GAP + render::TILE_SIZE / 2;
+
+        // the board, an empty tile, the `2` tile, and the middle of the `2`
+        assert_eq!(pixel(0, 0), [0xbb, 0xad, 0xa0, 0xff]);
+        assert_eq!(pixel(size - 13, size - 13), [0xcd, 0xc1, 0xb4, 0xff]);
+        assert_eq!(pixel(render::GAP, render::GAP), [0xee, 0xe4, 0xda, 0xff]);
+        assert_eq!(pixel(center, center), [0x77, 0x6e, 0x65, 0xff]);
+    }
+
+    #[test]
+    fn transpose() {
+        let mut cells = SAMPLE_GAME_BOARD;
+        super::transpose(&mut cells);
+
+        const EXPECTED: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] = [
+            [Tile(2), Tile(2), Vacant, Tile(2)],
+            [Tile(2), Tile(8), Vacant, Tile(4)],
+            [Tile(2), Tile(1), Vacant, Tile(1)],
+            [Tile(2), Vacant, Vacant, Tile(2)],
+        ];
+
+        assert_eq!(cells, EXPECTED);
+
+        super::transpose(&mut cells);
+        assert_eq!(cells, SAMPLE_GAME_BOARD);
+    }
+
+    #[test]
+    fn slide_row() {
+        let mut row = [Tile(2), Vacant, Tile(2), Tile(2)];
+        let mut outcome = MoveOutcome::default();
+
+        super::slide_row(&mut row, &GameConfig::default(), &mut outcome);
+
+        assert_eq!(row, [Tile(4), Tile(2), Vacant, Vacant]);
+        assert!(outcome.moved);
+        assert_eq!(outcome.score_delta, 4);
+    }
+
+    #[test]
+    fn animation_frames() {
+        let board = no_spawns(SAMPLE_GAME_BOARD);
+
+        let frames = board.animation_frames(MoveDirection::Down);
+        assert_eq!(frames.len(), 4);
+
+        let mut moved = board.clone();
+        moved.r#move(MoveDirection::Down);
+        assert_eq!(frames.last(), Some(&moved.cells));
+        assert_eq!(board.cells, SAMPLE_GAME_BOARD);
+
+        let board = no_spawns([
+            [Tile(2), Tile(4), Tile(8), Tile(16)],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+        ]);
+        assert!(board.animation_frames(MoveDirection::Up).is_empty());
+    }
+
+    #[test]
+    fn slide_plan_wrap() {
+        let row = [Tile(2), Vacant, Tile(4), Tile(2)];
+        let wrap = GameConfig {
+            wrap: true,
+            ..Default::default()
+        };
+        let no_wrap = GameConfig::default();
+
+        assert_eq!(slide_plan(&row, &wrap), [(0, 0, 2), (2, 1, 4), (3, 3, 2)]);
+        assert_eq!(
+            slide_plan(&row, &no_wrap),
+            [(0, 0, 2), (2, 1, 4), (3, 2, 2)]
+        );
+
+        let row = [Tile(2), Tile(2), Tile(2), Tile(2)];
+        assert_eq!(
+            slide_plan(&row, &wrap),
+            [(0, 0, 2), (1, 0, 2), (2, 1, 2), (3, 1, 2)]
+        );
+    }
+
+    #[test]
+    fn slide_row_wrap() {
+        let wrap = GameConfig {
+            wrap: true,
+            ..Default::default()
+        };
+        let mut row = [Tile(2), Tile(4), Tile(2), Vacant];
+        let mut outcome = MoveOutcome::default();
+
+        super::slide_row(&mut row, &wrap, &mut outcome);
+
+        assert_eq!(row, [Tile(4), Tile(4), Vacant, Vacant]);
+        assert_eq!(outcome.score_delta, 4);
+
+        // tiles that already merged don't merge again across the edge
+        let mut row = [Tile(2), Tile(2), Tile(2), Tile(2)];
+        let mut outcome = MoveOutcome::default();
+
+        super::slide_row(&mut row, &wrap, &mut outcome);
+
+        assert_eq!(row, [Tile(4), Tile(4), Vacant, Vacant]);
+        assert_eq!(outcome.merges, 2);
+
+        let mut row = [Tile(2), Tile(2), Tile(2), Vacant];
+        let mut outcome = MoveOutcome::default();
+
+        super::slide_row(&mut row, &wrap, &mut outcome);
+
+        assert_eq!(row, [Tile(4), Tile(2), Vacant, Vacant]);
+    }
+
+    #[test]
+    fn min_merge_value() {
+        let config = GameConfig {
+            min_merge_value: 8,
+            spawn_per_move: 0,
+            ..Default::default()
+        };
+
+        let mut row = [Tile(4), Vacant, Tile(4), Tile(8)];
+        let mut outcome = MoveOutcome::default();
+        super::slide_row(&mut row, &config, &mut outcome);
+
+        // the 4s slide together without merging, and the 8 has nothing
+        // to merge with
+        assert_eq!(row, [Tile(4), Tile(4), Tile(8), Vacant]);
+        assert!(outcome.moved);
+        assert_eq!(outcome.merges, 0);
+
+        let mut cells = LOST_GAME_BOARD;
+        cells[0] = [Tile(8), Tile(8), Tile(4), Tile(4)];
+        let mut board =
+            GameBoard::with_cells_and_config(config.clone(), Some(cells), 0);
+        assert_eq!(
+            board.animation_frames(MoveDirection::Left).last(),
+            Some(&board.preview_cells(MoveDirection::Left))
+        );
+        assert_eq!(board.r#move(MoveDirection::Left).score_delta, 16);
+        assert_eq!(board.cells[0], [Tile(16), Tile(4), Tile(4), Vacant]);
+
+        // pairs of small tiles don't count as moves
+        board.set((3, 0), Tile(16));
+        assert!(board.has_lost());
+
+        let board = GameBoard::with_cells_and_config(
+            GameConfig {
+                wrap: true,
+                ..config
+            },
+            Some([
+                [Tile(4), Tile(8), Tile(16), Tile(4)],
+                [Tile(2), Tile(4), Tile(2), Tile(8)],
+                [Tile(4), Tile(2), Tile(4), Tile(2)],
+                [Tile(2), Tile(4), Tile(2), Tile(8)],
+            ]),
+            0,
+        );
+        assert!(board.has_lost());
+    }
+
+    #[test]
+    fn wrap_has_lost() {
+        const FULL: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] = [
+            [Tile(2), Tile(4), Tile(8), Tile(2)],
+            [Tile(4), Tile(8), Tile(2), Tile(16)],
+            [Tile(8), Tile(2), Tile(4), Tile(32)],
+            [Tile(16), Tile(32), Tile(64), Tile(128)],
+        ];
+
+        assert!(no_spawns(FULL).has_lost());
+
+        let config = GameConfig {
+            wrap: true,
+            spawn_per_move: 0,
+            ..Default::default()
+        };
+        let mut board = GameBoard::with_cells_and_config(config, Some(FULL), 0);
+
+        assert!(!board.has_lost());
+        assert_eq!(
+            board.legal_moves(),
+            vec![MoveDirection::Left, MoveDirection::Right]
+        );
+
+        board.r#move(MoveDirection::Left);
+        assert_eq!(board.cells[0], [Tile(4), Tile(4), Tile(8), Vacant]);
+    }
+
+    #[test]
+    fn merge_until_stable() {
+        let cells = [
+            [Tile(4), Tile(2), Tile(2), Tile(8)],
+            [Tile(2), Tile(4), Tile(8), Tile(16)],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+        ];
+
+        // the default rules stop after one slide
+        let mut board = no_spawns(cells);
+        assert_eq!(board.merge_until_stable(MoveDirection::Left), 1);
+        assert_eq!(board.cells[0], [Tile(4), Tile(4), Tile(8), Vacant]);
+
+        let config = GameConfig {
+            spawn_per_move: 0,
+            cascade_merges: true,
+            ..Default::default()
+        };
+        let mut board =
+            GameBoard::with_cells_and_config(config, Some(cells), 0);
+        let version = board.version();
+
+        // 4 2 2 8 -> 4 4 8 -> 8 8 -> 16
+        assert_eq!(board.merge_until_stable(MoveDirection::Left), 3);
+        assert_eq!(board.cells[0], [Tile(16), Vacant, Vacant, Vacant]);
+        assert_eq!(board.cells[1], [Tile(2), Tile(4), Tile(8), Tile(16)]);
+        assert_eq!(board.score, 4 + 8 + 16);
+        assert_eq!(board.moves(), 0);
+        assert!(board.version() > version);
+
+        let version = board.version();
+        assert_eq!(board.merge_until_stable(MoveDirection::Left), 0);
+        assert_eq!(board.version(), version);
+
+        assert_eq!(board.undo(), Ok(()));
+        assert_eq!(board.cells, cells);
+        assert_eq!(board.score, 0);
+    }
+
+    #[test]
+    fn try_merge_cells() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+
+        assert_eq!(board.try_merge_cells((0, 1), (0, 0)), Ok(true));
+        assert_eq!(board.get((0, 1)), Tile(4));
+        assert_eq!(board.get((0, 0)), Vacant);
+        assert_eq!(board.score, 4);
+        assert_eq!(board.biggest_merge(), 4);
+
+        // vacant spaces and different tiles don't merge
+        assert_eq!(board.try_merge_cells((0, 1), (0, 0)), Ok(false));
+        assert_eq!(board.try_merge_cells((1, 1), (2, 1)), Ok(false));
+        assert_eq!(board.score, 4);
+
+        assert_eq!(
+            board.try_merge_cells((3, 0), (4, 0)),
+            Err(MergeError::OutOfBounds)
+        );
+        assert_eq!(
+            board.try_merge_cells((1, 0), (2, 1)),
+            Err(MergeError::NotAdjacent)
+        );
+        assert_eq!(
+            board.try_merge_cells((1, 0), (1, 0)),
+            Err(MergeError::NotAdjacent)
+        );
+
+        // it's scored the same as the merge a move would make
+        let mut cells = [[Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE];
+        cells[0][0] = Tile(8);
+        cells[0][1] = Tile(8);
+        let mut moved = no_spawns(cells);
+        let mut merged = no_spawns(cells);
+        moved.r#move(MoveDirection::Left);
+        merged.try_merge_cells((0, 0), (1, 0)).unwrap();
+        assert_eq!(merged.cells, moved.cells);
+        assert_eq!(merged.score, moved.score);
+        assert_eq!(merged.score_breakdown(), moved.score_breakdown());
+        assert_eq!(merged.biggest_merge(), moved.biggest_merge());
+
+        let config = GameConfig {
+            wrap: true,
+            spawn_per_move: 0,
+            ..Default::default()
+        };
+        let mut board = GameBoard::with_cells_and_config(
+            config,
+            Some(SAMPLE_GAME_BOARD),
+            0,
+        );
+        assert_eq!(board.try_merge_cells((0, 3), (0, 0)), Ok(true));
+        assert_eq!(board.try_merge_cells((3, 0), (0, 0)), Ok(false));
+        assert_eq!(board.score, 4);
+    }
+
+    #[test]
+    fn clear_row_and_column() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+
+        assert_eq!(board.clear_column(1, false), Some(14));
+        assert_eq!(board.score, 0);
+        assert_eq!(
+            board.cells,
+            [
+                [Tile(2), Vacant, Tile(2), Tile(2)],
+                [Tile(2), Vacant, Tile(1), Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+                [Tile(2), Vacant, Tile(1), Tile(2)],
+            ]
+        );
+
+        assert_eq!(board.clear_row(3, true), Some(5));
+        assert_eq!(board.score, 5);
+        let version = board.version();
+        assert_eq!(board.clear_row(2, true), Some(0));
+        assert_eq!(board.version(), version);
+        board.clear_row(0, false);
+        assert_eq!(board.version(), version + 1);
+
+        assert_eq!(board.clear_row(GAME_BOARD_SIZE, true), None);
+        assert_eq!(board.clear_column(GAME_BOARD_SIZE, true), None);
+        assert_eq!(board.score, 5);
+
+        // cleared spaces are empty, so tiles slide into them
+        let mut board = GameBoard::from(SAMPLE_GAME_BOARD);
+        board.clear_column(0, false);
+        board.set_spawn_queue(vec![(Some((3, 2)), 4)]);
+        board.r#move(MoveDirection::Left);
+        assert_eq!(
+            board.cells,
+            [
+                [Tile(4), Tile(2), Vacant, Vacant],
+                [Tile(8), Tile(1), Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Tile(4)],
+                [Tile(4), Tile(1), Tile(2), Vacant],
+            ]
+        );
+    }
+
+    #[test]
+    fn apply_gravity() {
+        const NO_MERGES: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] = [
+            [Tile(2), Vacant, Tile(4), Tile(8)],
+            [Vacant, Tile(8), Vacant, Tile(4)],
+            [Tile(16), Vacant, Vacant, Vacant],
+            [Vacant, Tile(2), Tile(8), Tile(2)],
+        ];
+
+        for dir in MoveDirection::all() {
+            let mut moved = no_spawns(NO_MERGES);
+            moved.r#move(dir);
+
+            let mut compacted = no_spawns(NO_MERGES);
+            assert!(compacted.apply_gravity(dir));
+
+            assert_eq!(compacted.cells, moved.cells);
+            assert_eq!(compacted.moves(), 0);
+            assert_eq!(compacted.version(), 1);
+        }
+
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+        board.apply_gravity(MoveDirection::Left);
+        assert_eq!(board.cells[0], [Tile(2), Tile(2), Tile(2), Tile(2)]);
+        assert_eq!(board.score, 0);
+
+        let version = board.version();
+        assert!(!board.apply_gravity(MoveDirection::Left));
+        assert_eq!(board.version(), version);
+    }
+
+    #[test]
+    fn format_score() {
+        assert_eq!(super::format_score(0, Some(',')), "0");
+        assert_eq!(super::format_score(999, Some(',')), "999");
+        assert_eq!(super::format_score(1000, Some(',')), "1,000");
+        assert_eq!(super::format_score(3932100, Some('.')), "3.932.100");
+        assert_eq!(super::format_score(3932100, None), "3932100");
+    }
+
+    #[test]
+    fn spawn_policy_avoid_highest_tile() {
+        let config = GameConfig {
+            spawn_policy: SpawnPolicy::AvoidHighestTile,
+            ..Default::default()
+        };
+        let mut board = GameBoard::with_cells_and_config(
+            config,
+            Some([
+                [Tile(64), Vacant, Tile(2), Tile(4)],
+                [Vacant, Tile(8), Tile(4), Tile(2)],
+                [Tile(2), Tile(4), Vacant, Tile(4)],
+                [Tile(4), Tile(2), Tile(4), Tile(2)],
+            ]),
+            0,
+        );
+
+        assert_eq!(board.spawn_candidates(2), vec![(2, 2)]);
+
+        // falls back to every vacant space when they're all next to it
+        board.set((2, 2), Tile(16));
+        assert_eq!(board.spawn_candidates(2), vec![(1, 0), (0, 1)]);
+    }
+
+    #[test]
+    fn spawn_policy_keep_alive() {
+        let config = GameConfig {
+            spawn_policy: SpawnPolicy::KeepAlive,
+            allowed_directions: [true, false, false, false],
+            ..Default::default()
+        };
+        let mut cells = LOST_GAME_BOARD;
+        cells[0] = [Tile(8), Tile(16), Vacant, Vacant];
+        let mut board =
+            GameBoard::with_cells_and_config(config, Some(cells), 0);
+
+        // a tile at (2, 0) would leave nothing to slide left
+        assert_eq!(board.spawn_candidates(2), vec![(3, 0)]);
+        // but a 16 there could merge
+        assert_eq!(board.spawn_candidates(16), vec![(2, 0), (3, 0)]);
+
+        // falls back to every vacant space when the game ends either way
+        board.set((3, 0), Tile(32));
+        assert_eq!(board.spawn_candidates(2), vec![(2, 0)]);
+    }
+
+    #[test]
+    fn get() {
+        let board = no_spawns(SAMPLE_GAME_BOARD);
+        let got = board.get((0, 0));
+        assert_eq!(got, BoardSpace::Tile(2));
+    }
+
+    #[test]
+    fn set() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+        board.set((0, 0), BoardSpace::Tile(2048));
+
+        const EXPECTED: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] = [
+            [Tile(2048), Tile(2), Tile(2), Tile(2)],
+            [Tile(2), Tile(8), Tile(1), Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Tile(2), Tile(4), Tile(1), Tile(2)],
+        ];
+
+        assert_eq!(board.cells, EXPECTED);
+    }
+
+    #[test]
+    fn layout_accessors() {
+        let board = no_spawns(SAMPLE_GAME_BOARD);
+
+        assert_eq!(board.size(), GAME_BOARD_SIZE);
+        assert_eq!(board.row(1), &SAMPLE_GAME_BOARD[1]);
+        assert!(board.rows().eq(SAMPLE_GAME_BOARD.iter().map(|r| &r[..])));
+
+        assert_eq!(board.spaces().count(), GAME_BOARD_SIZE * GAME_BOARD_SIZE);
+        for ((x, y), space) in board.spaces() {
+            assert_eq!(space, SAMPLE_GAME_BOARD[y][x]);
+            assert_eq!(space, board.get((x, y)));
+        }
+    }
+
+    #[test]
+    fn direction_vectors() {
+        assert_eq!(MoveDirection::Left.as_vector(), (-1, 0));
+        assert_eq!(MoveDirection::Up.as_vector(), (0, -1));
+        assert_eq!(MoveDirection::Right.as_vector(), (1, 0));
+        assert_eq!(MoveDirection::Down.as_vector(), (0, 1));
+
+        // sliding a lone tile lands it at the edge the vector points to
+        for dir in MoveDirection::all() {
+            let mut board =
+                no_spawns([[Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE]);
+            board.set((1, 1), Tile(2));
+            board.r#move(dir);
+
+            let (dx, dy) = dir.as_vector();
+            let edge = |d: isize| match d {
+                -1 => 0,
+                0 => 1,
+                _ => GAME_BOARD_SIZE - 1,
+            };
+            assert_eq!(board.get((edge(dx), edge(dy))), Tile(2));
+        }
+    }
+
+    #[test]
+    fn neighbors() {
+        let board = no_spawns(SAMPLE_GAME_BOARD);
+
+        let corner: Vec<_> = board.neighbors((0, 0)).collect();
+        assert_eq!(corner, vec![((1, 0), Tile(2)), ((0, 1), Tile(2))]);
+
+        let edge: Vec<_> = board.neighbors((3, 1)).collect();
+        assert_eq!(
+            edge,
+            vec![((2, 1), Tile(1)), ((3, 0), Tile(2)), ((3, 2), Vacant)]
+        );
+
+        let interior: Vec<_> = board.neighbors((1, 2)).collect();
+        assert_eq!(
+            interior,
+            vec![
+                ((0, 2), Vacant),
+                ((1, 1), Tile(8)),
+                ((2, 2), Vacant),
+                ((1, 3), Tile(4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn legal_move_mask() {
+        let board = no_spawns(SAMPLE_GAME_BOARD);
+        let mask = board.legal_move_mask();
+
+        for dir in MoveDirection::all() {
+            assert_eq!(mask[dir as usize], board.legal_moves().contains(&dir));
+        }
+
+        let lost = no_spawns(LOST_GAME_BOARD);
+        assert!(lost.has_lost());
+        assert_eq!(lost.legal_move_mask(), [false; 4]);
+
+        let empty = no_spawns([[Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE]);
+        assert!(empty.has_lost());
+        assert_eq!(empty.legal_move_mask(), [false; 4]);
+    }
+
+    #[test]
+    fn move_loss_risk() {
+        let mut board = GameBoard::from([
+            [Tile(2), Tile(4), Tile(8), Tile(16)],
+            [Tile(4), Tile(8), Tile(16), Tile(32)],
+            [Tile(8), Tile(16), Tile(32), Tile(64)],
+            [Vacant, Tile(32), Tile(64), Tile(128)],
+        ]);
+
+        // the only space left after sliding left can't merge with a 2 or 4
+        assert!(board.move_risks_loss(MoveDirection::Left));
+        assert!(board.move_guarantees_loss(MoveDirection::Left));
+
+        // the space left at the top can merge with either
+        assert!(!board.move_risks_loss(MoveDirection::Down));
+        assert!(!board.move_guarantees_loss(MoveDirection::Down));
+
+        // moves that change nothing spawn nothing
+        assert!(!board.move_risks_loss(MoveDirection::Up));
+        assert!(!board.move_guarantees_loss(MoveDirection::Up));
+
+        // a 4 spawning under this one keeps the game going
+        board.set((3, 2), Tile(4));
+        assert!(board.move_risks_loss(MoveDirection::Left));
+        assert!(!board.move_guarantees_loss(MoveDirection::Left));
+
+        // the check doesn't change the board
+        assert_eq!(board.get((0, 3)), Vacant);
+        assert_eq!(board.score, 0);
+    }
+
+    #[test]
+    fn move_loss_risk_follows_the_rules() {
+        let cells = [
+            [Tile(2), Tile(4), Tile(8), Tile(16)],
+            [Tile(4), Tile(8), Tile(16), Tile(32)],
+            [Tile(8), Tile(16), Tile(32), Tile(64)],
+            [Vacant, Tile(32), Tile(64), Tile(128)],
+        ];
+
+        // a combo doesn't spawn, so the space left after sliding stays free
+        let config = GameConfig {
+            combo: Some(Combo {
+                window: ComboWindow::Moves(1),
+                multiplier: 2,
+            }),
+            ..Default::default()
+        };
+        let mut board =
+            GameBoard::with_cells_and_config(config, Some(cells), 0);
+        assert!(board.move_guarantees_loss(MoveDirection::Left));
+        board.last_merge = Some(LastMerge {
+            moves: board.moves(),
+            at: None,
+        });
+        assert!(!board.move_risks_loss(MoveDirection::Left));
+        assert!(!board.move_guarantees_loss(MoveDirection::Left));
+        assert!(board.r#move(MoveDirection::Left).combo);
+        assert!(!board.has_lost());
+
+        // the 32 on the right of the second row decays into a 16 that can
+        // merge with its neighbour, whatever spawns
+        let config = GameConfig {
+            decay: Some(Decay {
+                after_moves: 3,
+                floor: DecayFloor::Keep,
+            }),
+            ..Default::default()
+        };
+        let mut board =
+            GameBoard::with_cells_and_config(config, Some(cells), 0);
+        assert!(board.move_guarantees_loss(MoveDirection::Left));
+        board.ages[1][3] = 2;
+        assert!(!board.move_risks_loss(MoveDirection::Left));
+        assert!(!board.move_guarantees_loss(MoveDirection::Left));
+        assert_eq!(board.r#move(MoveDirection::Left).decayed, 1);
+        assert!(!board.has_lost());
+    }
+
+    #[test]
+    fn preview_cells() {
+        let mut board = GameBoard::with_seed(5);
+        board.cells = SAMPLE_GAME_BOARD;
+        let rng = board.rng_state();
+
+        let preview = board.preview_cells(MoveDirection::Left);
+        assert_eq!(preview[0], [Tile(4), Tile(4), Vacant, Vacant]);
+        assert_eq!(board.cells, SAMPLE_GAME_BOARD);
+        assert_eq!(board.score, 0);
+        assert_eq!(board.rng_state(), rng);
+
+        board.r#move(MoveDirection::Left);
+        for loc in board.last_spawns.clone() {
+            board.set(loc, Vacant);
+        }
+        assert_eq!(board.cells, preview);
+
+        // directions that aren't allowed don't move anything
+        let config = GameConfig {
+            allowed_directions: [false, true, true, true],
+            ..Default::default()
+        };
+        let board = GameBoard::with_cells_and_config(
+            config,
+            Some(SAMPLE_GAME_BOARD),
+            0,
+        );
+        assert_eq!(board.preview_cells(MoveDirection::Left), SAMPLE_GAME_BOARD);
+    }
+
+    #[test]
+    fn all_empty_spaces() {
+        let board = no_spawns(SAMPLE_GAME_BOARD);
+
+        let expected = vec![(3, 1), (0, 2), (1, 2), (2, 2), (3, 2)];
+
+        let got = board.all_empty_spaces();
+        assert_eq!(got, expected);
+    }
+
+    #[test]
+    fn all_empty_spaces_is_row_major() {
+        let board = no_spawns([
+            [Tile(2), Vacant, Tile(2), Tile(2)],
+            [Vacant, Tile(2), Tile(2), Vacant],
+            [Tile(2), Tile(2), Tile(2), Tile(2)],
+            [Tile(2), Vacant, Tile(2), Vacant],
+        ]);
+
+        let expected = vec![(1, 0), (0, 1), (3, 1), (1, 3), (3, 3)];
+
+        let got = board.all_empty_spaces();
+        assert_eq!(got, expected);
+    }
+
+    #[test]
+    fn with_cells_and_config_uses_cells_verbatim() {
+        let config = GameConfig {
+            starting_tiles: 5,
+            ..Default::default()
+        };
+        let board = GameBoard::with_cells_and_config(
+            config.clone(),
+            Some(SAMPLE_GAME_BOARD),
+            12,
+        );
+
+        assert_eq!(board.cells, SAMPLE_GAME_BOARD);
+        assert_eq!(board.score, 12);
+        assert_eq!(board.config(), &config);
+    }
+
+    #[test]
+    fn share_code() {
+        let board = GameBoard::with_cells_and_config(
+            GameConfig::default(),
+            Some(SAMPLE_GAME_BOARD),
+            1234,
+        );
+        let code = board.to_share_code();
+        assert!(code.len() < 32);
+
+        let shared = GameBoard::from_share_code(&code).unwrap();
+        assert_eq!(shared.cells, SAMPLE_GAME_BOARD);
+        assert_eq!(shared.score, 1234);
+        assert_eq!(shared.config(), board.config());
+
+        let config = GameConfig {
+            spawn_distribution: vec![(2, 0.5), (8, 0.5)],
+            win_target: 4096,
+            win_behavior: WinBehavior::Prompt,
+            seed: Some(u64::MAX),
+            wrap: true,
+            spawn_policy: SpawnPolicy::KeepAlive,
+            move_limit: Some(300),
+            allowed_directions: [true, false, true, true],
+            direction_cycle: vec![MoveDirection::Down, MoveDirection::Left],
+            undos: Some(3),
+            min_merge_value: 4,
+            per_move_score: 1,
+            spawn_score: SpawnScore::Flat(10),
+            decay: Some(Decay {
+                after_moves: 12,
+                floor: DecayFloor::Keep,
+            }),
+            combo: Some(Combo {
+                window: ComboWindow::Time(Duration::from_millis(1500)),
+                multiplier: 3,
+            }),
+            cascade_merges: true,
+            ..Default::default()
+        };
+        let mut cells = SAMPLE_GAME_BOARD;
+        cells[2][2] = Tile(131072);
+        let board =
+            GameBoard::with_cells_and_config(config.clone(), Some(cells), 7);
+
+        let shared =
+            GameBoard::from_share_code(&board.to_share_code()).unwrap();
+        assert_eq!(shared.cells, cells);
+        assert_eq!(shared.score, 7);
+        assert_eq!(*shared.config(), config);
+    }
+
+    #[test]
+    fn share_code_errors() {
+        let code = no_spawns(SAMPLE_GAME_BOARD).to_share_code();
+
+        // the version is the first byte, in the first two characters
+        let mut newer = String::from("Bw");
+        newer.push_str(&code[2..]);
+        assert_eq!(
+            GameBoard::from_share_code(&newer).unwrap_err(),
+            ShareCodeError::UnsupportedVersion(7)
+        );
+
+        // older versions are still read, and boards with the default rules
+        // are written the same way in every version
+        let default_code = GameBoard::from(SAMPLE_GAME_BOARD).to_share_code();
+        let mut older = String::from("AQ");
+        older.push_str(&default_code[2..]);
+        let board = GameBoard::from_share_code(&older).unwrap();
+        assert_eq!(board.config().min_merge_value, 0);
+
+        assert_eq!(
+            GameBoard::from_share_code(&code[..code.len() - 4]).unwrap_err(),
+            ShareCodeError::Malformed
+        );
+        assert_eq!(
+            GameBoard::from_share_code(&format!("{code}AA")).unwrap_err(),
+            ShareCodeError::Malformed
+        );
+        assert_eq!(
+            GameBoard::from_share_code("").unwrap_err(),
+            ShareCodeError::Malformed
+        );
+        assert_eq!(
+            GameBoard::from_share_code("AB+C").unwrap_err(),
+            ShareCodeError::InvalidCharacter('+')
+        );
+    }
+
+    #[test]
+    fn debug_code() {
+        let config = GameConfig {
+            seed: Some(5),
+            undos: Some(2),
+            direction_cycle: vec![MoveDirection::Down, MoveDirection::Right],
+            decay: Some(Decay {
+                after_moves: 3,
+                floor: DecayFloor::Keep,
+            }),
+            combo: Some(Combo {
+                window: ComboWindow::Moves(2),
+                multiplier: 2,
+            }),
+            ..Default::default()
+        };
+        let mut board = GameBoard::with_cells_and_config(config, None, 0);
+        board.next_scripted_direction();
+        board.r#move(MoveDirection::Left);
+        board.undo().unwrap();
+        board.r#move(MoveDirection::Up);
+
+        let code = board.debug_code();
+        let mut rebuilt = GameBoard::from_debug_code(&code).unwrap();
+        assert_eq!(rebuilt.debug_code(), code);
+        assert_eq!(rebuilt.config(), board.config());
+        assert_eq!(rebuilt.rng_state(), board.rng_state());
+        assert_eq!(rebuilt.undos_remaining(), Some(1));
+        assert_eq!(rebuilt.ages, board.ages);
+
+        // the next move plays out the same on both boards
+        let dir = board.next_scripted_direction();
+        assert_eq!(rebuilt.next_scripted_direction(), dir);
+        board.r#move(dir);
+        rebuilt.r#move(dir);
+        assert_eq!(rebuilt.cells, board.cells);
+        assert_eq!(rebuilt.score, board.score);
+
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+        board.end_game();
+        let rebuilt = GameBoard::from_debug_code(&board.debug_code()).unwrap();
+        assert!(rebuilt.is_over());
+    }
+
+    #[test]
+    fn debug_code_v1() {
+        // written before the rules carried their own version, when they
+        // were laid out like a version 3 share code
+        let code = "d1 3,.,.,./.,.,.,./.,.,.,./.,.,1,. score=12 moves=0 \
+                    undos=3 config=AgICzczMzMzM7D8EmpmZmZmZuT-AEAIBAAEAAAAP\
+                    BAABAgMBAwQCAA";
+
+        let board = GameBoard::from_debug_code(code).unwrap();
+        assert_eq!(board.get((0, 0)), Tile(8));
+        assert_eq!(board.get((2, 3)), Tile(2));
+        assert_eq!(board.score, 12);
+        assert_eq!(board.undos_remaining(), Some(3));
+        assert_eq!(
+            *board.config(),
+            GameConfig {
+                wrap: true,
+                undos: Some(3),
+                min_merge_value: 4,
+                per_move_score: 2,
+                ..Default::default()
+            }
+        );
+
+        let code = board.debug_code();
+        assert!(code.starts_with("d2 "));
+        assert!(code.contains(" config=6:"));
+        assert_eq!(
+            GameBoard::from_debug_code(&code).unwrap().config(),
+            board.config()
+        );
+    }
+
+    #[test]
+    fn debug_code_errors() {
+        let code = GameBoard::from(SAMPLE_GAME_BOARD).debug_code();
+
+        assert_eq!(
+            GameBoard::from_debug_code(&code.replacen("d2", "d3", 1))
+                .unwrap_err(),
+            ShareCodeError::UnsupportedVersion(3)
+        );
+        assert_eq!(
+            GameBoard::from_debug_code(&format!("{code} config=9:AA"))
+                .unwrap_err(),
+            ShareCodeError::UnsupportedVersion(9)
+        );
+
+        for code in [
+            "",
+            "1,1,1,1 score=0",
+            "d1 1,1,1,1",
+            "d1 .,.,.,./.,.,.,./.,.,.,./.,.,.,.,.",
+            "d1 .,.,.,./.,.,.,./.,.,.,./.,.,.,99",
+            "d1 .,.,.,./.,.,.,./.,.,.,./.,.,.,#0",
+            "d1 .,.,.,./.,.,.,./.,.,.,./.,.,.,. cheats=on",
+            "d1 .,.,.,./.,.,.,./.,.,.,./.,.,.,. rng=7",
+            "d2 .,.,.,./.,.,.,./.,.,.,./.,.,.,. config=AA",
+        ] {
+            assert_eq!(
+                GameBoard::from_debug_code(code).unwrap_err(),
+                ShareCodeError::Malformed,
+                "{code:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn bytes() {
+        let board = no_spawns([
+            [Tile(2), Tile(4), Vacant, Vacant],
+            [Vacant, Tile(32768), Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Tile(8)],
+        ]);
+        let bytes = board.to_bytes();
+        let rebuilt = GameBoard::from_bytes(&bytes).unwrap();
+        assert_eq!(rebuilt.cells, board.cells);
+        assert_eq!(rebuilt.config(), board.config());
+        assert_eq!(GameBoard::from(board.cells).to_bytes().len(), 14);
+
+        let config = GameConfig {
+            seed: Some(11),
+            wrap: true,
+            decay: Some(Decay {
+                after_moves: 4,
+                floor: DecayFloor::Keep,
+            }),
+            ..Default::default()
+        };
+        let mut cells = SAMPLE_GAME_BOARD;
+        cells[2][0] = Tile(2048);
+        let mut board =
+            GameBoard::with_cells_and_config(config, Some(cells), 50);
+        board.r#move(MoveDirection::Left);
+        board.r#move(MoveDirection::Up);
+        board.end_game();
+
+        let rebuilt = GameBoard::from_bytes(&board.to_bytes()).unwrap();
+        assert_eq!(rebuilt.cells, board.cells);
+        assert_eq!(rebuilt.score, board.score);
+        assert_eq!(rebuilt.moves(), board.moves());
+        assert_eq!(rebuilt.version(), board.version());
+        assert_eq!(rebuilt.biggest_merge(), board.biggest_merge());
+        assert_eq!(rebuilt.ages, board.ages);
+        assert_eq!(rebuilt.config(), board.config());
+        assert_eq!(rebuilt.rng_state(), board.rng_state());
+        assert!(rebuilt.ever_won());
+        assert!(rebuilt.is_over());
+        assert_eq!(rebuilt.to_bytes(), board.to_bytes());
+
+        // undos are only written once some have been used
+        let config = GameConfig {
+            undos: Some(3),
+            ..Default::default()
+        };
+        let mut board = GameBoard::with_cells_and_config(
+            config,
+            Some(SAMPLE_GAME_BOARD),
+            0,
+        );
+        let fresh = board.to_bytes();
+        assert_eq!(
+            GameBoard::from_bytes(&fresh).unwrap().undos_remaining(),
+            Some(3)
+        );
+        board.r#move(MoveDirection::Left);
+        board.undo().unwrap();
+        let bytes = board.to_bytes();
+        assert_eq!(bytes.len(), fresh.len() + 1);
+        let rebuilt = GameBoard::from_bytes(&bytes).unwrap();
+        assert_eq!(rebuilt.undos_remaining(), Some(2));
+        assert_eq!(rebuilt.to_bytes(), bytes);
+
+        // the rules come after everything a board with the default rules
+        // writes, starting with the share code version they were written
+        // with
+        let config = GameConfig {
+            wrap: true,
+            ..Default::default()
+        };
+        let plain = GameBoard::from(SAMPLE_GAME_BOARD).to_bytes();
+        let mut bytes = GameBoard::with_cells_and_config(
+            config,
+            Some(SAMPLE_GAME_BOARD),
+            0,
+        )
+        .to_bytes();
+        assert_eq!(bytes[plain.len()], 6);
+        bytes[plain.len()] = 7;
+        assert_eq!(
+            GameBoard::from_bytes(&bytes).unwrap_err(),
+            ShareCodeError::UnsupportedVersion(7)
+        );
+
+        // the messages don't assume which format was being read
+        assert_eq!(
+            ShareCodeError::UnsupportedVersion(7).to_string(),
+            "format version 7 isn't supported"
+        );
+        assert_eq!(
+            ShareCodeError::Malformed.to_string(),
+            "saved board is damaged"
+        );
+
+        let bytes = board.to_bytes();
+        for bytes in [
+            &bytes[..bytes.len() - 1],
+            &[bytes.clone(), vec![0]].concat(),
+        ] {
+            assert_eq!(
+                GameBoard::from_bytes(bytes).unwrap_err(),
+                ShareCodeError::Malformed
+            );
+        }
+        assert_eq!(
+            GameBoard::from_bytes(&[]).unwrap_err(),
+            ShareCodeError::Malformed
+        );
+    }
+
+    #[test]
+    fn new_with_rng() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut board = GameBoard::new_with_rng(StdRng::seed_from_u64(3));
+        assert_eq!(
+            board.cells,
+            GameBoard::new_with_rng(StdRng::seed_from_u64(3)).cells
+        );
+        assert_eq!(board.empty_count(), 14);
+
+        // undoing a move rewinds the generator too, so the same spawn is
+        // picked when the move is made again
+        let dir = board.legal_moves()[0];
+        board.r#move(dir);
+        let moved = board.cells;
+
+        board.undo().unwrap();
+        board.r#move(dir);
+        assert_eq!(board.cells, moved);
+    }
+
+    #[test]
+    fn restore_rng_state() {
+        let mut original = GameBoard::with_seed(1234);
+        for _ in 0..10 {
+            original.rng.next_u64();
+        }
+
+        let mut restored =
+            no_spawns([[Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE]);
+        assert_eq!(restored.rng_state(), None);
+
+        restored.restore_rng_state(original.rng_state().unwrap());
+        assert_eq!(restored.rng_state(), original.rng_state());
+        assert_eq!(restored.config().seed, Some(1234));
+
+        for _ in 0..10 {
+            assert_eq!(restored.rng.next_u64(), original.rng.next_u64());
+        }
+    }
+
+    #[test]
+    fn checksum() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+        assert_eq!(board.checksum(), 0x486541cb);
+
+        board.r#move(MoveDirection::Left);
+        assert_ne!(board.checksum(), 0x486541cb);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let mut board = GameBoard::with_seed(99);
+        board.set((0, 0), Tile(2));
+        board.set((1, 0), Tile(2));
+        board.r#move(MoveDirection::Left);
+
+        let loaded = GameBoard::from_json(&board.to_json()).unwrap();
+
+        assert_eq!(loaded.cells, board.cells);
+        assert_eq!(loaded.score, board.score);
+        assert_eq!(loaded.moves(), board.moves());
+        assert_eq!(loaded.version(), board.version());
+        assert_eq!(loaded.biggest_merge(), board.biggest_merge());
+        assert_eq!(loaded.config(), board.config());
+        assert_eq!(loaded.rng_state(), board.rng_state());
+
+        let config = GameConfig {
+            undos: Some(3),
+            ..Default::default()
+        };
+        let mut board = GameBoard::with_cells_and_config(
+            config,
+            Some(SAMPLE_GAME_BOARD),
+            0,
+        );
+        board.r#move(MoveDirection::Left);
+        board.undo().unwrap();
+
+        let loaded = GameBoard::from_json(&board.to_json()).unwrap();
+        assert_eq!(loaded.undos_remaining(), Some(2));
+
+        // saves from before undos were kept start with all of them
+        let mut json = serde_json::to_value(&board).unwrap();
+        json["undos_remaining"] = serde_json::json!(null);
+        let json = serde_json::to_string(&json).unwrap();
+        let loaded = GameBoard::from_json(&json).unwrap();
+        assert_eq!(loaded.undos_remaining(), Some(3));
+
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+        assert!(!GameBoard::from_json(&board.to_json()).unwrap().is_over());
+        board.end_game();
+        let loaded = GameBoard::from_json(&board.to_json()).unwrap();
+        assert!(loaded.is_over());
+        assert_eq!(loaded.state(), GameState::Lost);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_format() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+        board.r#move(MoveDirection::Left);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&board.to_json()).unwrap();
+        assert_eq!(json["cells"][0][1].as_u64(), Some(4));
+        assert_eq!(json["cells"][1][3].as_u64(), Some(0));
+        assert_eq!(json["moves"].as_u64(), Some(1));
+        assert_eq!(json["config"]["spawn_policy"].as_str(), Some("Uniform"));
+        assert_eq!(
+            json["checksum"].as_u64(),
+            Some(u64::from(board.checksum()))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn ui_json() {
+        let board = no_spawns(SAMPLE_GAME_BOARD);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&board.to_ui_json()).unwrap();
+        assert_eq!(json["format"].as_u64(), Some(1));
+        assert_eq!(json["cells"][1][1].as_u64(), Some(8));
+        assert_eq!(json["cells"][2][0].as_u64(), Some(0));
+        assert_eq!(json["is_won"].as_bool(), Some(false));
+        assert_eq!(json["is_lost"].as_bool(), Some(false));
+        assert_eq!(json["highest_tile"].as_u64(), Some(8));
+        assert_eq!(
+            json["legal_moves"],
+            serde_json::json!(["Left", "Up", "Right", "Down"])
+        );
+
+        let board = no_spawns([[Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE]);
+        let json: serde_json::Value =
+            serde_json::from_str(&board.to_ui_json()).unwrap();
+        assert!(json["highest_tile"].is_null());
+        assert_eq!(json["is_lost"].as_bool(), Some(true));
+        assert_eq!(json["legal_moves"], serde_json::json!([]));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_checksum_mismatch() {
+        let board = no_spawns(SAMPLE_GAME_BOARD);
+
+        let mut json = serde_json::to_value(&board).unwrap();
+        json["score"] = 1_000_000.into();
+
+        let loaded = serde_json::from_value::<GameBoard>(json);
+        assert!(loaded.unwrap_err().to_string().contains("checksum"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_invalid_distribution() {
+        let board = no_spawns(SAMPLE_GAME_BOARD);
+
+        for distribution in
+            [serde_json::json!([]), serde_json::json!([[2, -1.0]])]
+        {
+            let mut json = serde_json::to_value(&board).unwrap();
+            json["config"]["spawn_distribution"] = distribution;
+
+            let json = serde_json::to_string(&json).unwrap();
+            let loaded = GameBoard::from_json(&json);
+            assert!(loaded.unwrap_err().to_string().contains("distribution"));
+        }
+    }
+
+    #[test]
+    fn max_achievable_tile() {
+        let board = no_spawns([[Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE]);
+        assert_eq!(board.max_achievable_tile(), 131072);
+    }
+
+    #[test]
+    fn move_timing() {
+        let clock = Arc::new(MockClock::default());
+        let mut board = no_spawns([[Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE]);
+        board.set((0, 0), Tile(2));
+
+        assert_eq!(board.total_play_time(), None);
+        assert_eq!(board.moves_per_minute(), 0.0);
+
+        board.enable_timing(clock.clone());
+        assert_eq!(board.moves_per_minute(), 0.0);
+        assert_eq!(board.total_play_time(), Some(Duration::ZERO));
+        assert_eq!(board.fastest_move(), None);
+
+        clock.advance(300);
+        board.r#move(MoveDirection::Right);
+        clock.advance(100);
+        board.r#move(MoveDirection::Left);
+
+        // nothing moves, so this isn't timed
+        clock.advance(1000);
+        board.r#move(MoveDirection::Left);
+
+        assert_eq!(board.total_play_time(), Some(Duration::from_millis(400)));
+        assert_eq!(board.fastest_move(), Some(Duration::from_millis(100)));
+        assert_eq!(board.average_move_time(), Some(Duration::from_millis(200)));
+        assert_eq!(board.moves_per_minute(), 300.0);
+    }
+
+    #[test]
+    fn score_per_move() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+        assert_eq!(board.score_per_move(), 0.0);
+
+        board.r#move(MoveDirection::Left);
+        board.r#move(MoveDirection::Left);
+        assert_eq!(board.score_per_move(), 8.0);
+    }
+
+    #[test]
+    fn move_outcome() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+
+        let outcome = board.r#move(MoveDirection::Left);
+        assert_eq!(
+            outcome,
+            MoveOutcome {
+                moved: true,
+                score_delta: 8,
+                extra_score: 0,
+                merges: 2,
+                merged_values: vec![4, 4],
+                max_line_merges: 2,
+                bonus_spawns: 0,
+                decayed: 0,
+                combo: false,
+                just_ended: false,
+                just_won: false,
+            }
+        );
+    }
+
+    #[test]
+    fn extra_score() {
+        let config = GameConfig {
+            per_move_score: 1,
+            spawn_score: SpawnScore::TileValue,
+            ..Default::default()
+        };
+        let mut board = GameBoard::with_cells_and_config(
+            config,
+            Some(SAMPLE_GAME_BOARD),
+            0,
+        );
+        board.set_spawn_queue(vec![(None, 4)]);
+
+        let outcome = board.r#move(MoveDirection::Left);
+        assert_eq!(outcome.score_delta, 8);
+        assert_eq!(outcome.extra_score, 5);
+        assert_eq!(board.score, 13);
+
+        // moves that change nothing don't score
+        let mut cells = [[Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE];
+        cells[0][0] = Tile(2);
+        let mut board = GameBoard::with_cells_and_config(
+            board.config().clone(),
+            Some(cells),
+            0,
+        );
+        let outcome = board.r#move(MoveDirection::Left);
+        assert_eq!(outcome.extra_score, 0);
+        assert_eq!(board.score, 0);
+
+        // the defaults score merges and nothing else
+        let mut board = GameBoard::from(SAMPLE_GAME_BOARD);
+        let outcome = board.r#move(MoveDirection::Left);
+        assert_eq!(outcome.extra_score, 0);
+        assert_eq!(board.score, 8);
+    }
+
+    #[test]
+    fn score_breakdown() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+
+        board.r#move(MoveDirection::Up);
+        board.r#move(MoveDirection::Left);
+        board.r#move(MoveDirection::Left);
+
+        let expected = BTreeMap::from([(2, 2), (4, 12), (8, 8)]);
+
+        assert_eq!(board.score_breakdown(), &expected);
+        assert_eq!(
+            board.score_breakdown().values().sum::<usize>(),
+            board.score
+        );
+    }
+
+    #[test]
+    fn score_breakdown_only_counts_merges() {
+        let config = GameConfig {
+            seed: Some(3),
+            per_move_score: 1,
+            spawn_score: SpawnScore::Flat(3),
+            combo: Some(Combo {
+                window: ComboWindow::Moves(1),
+                multiplier: 2,
+            }),
+            ..Default::default()
+        };
+        let cells = [
+            [Tile(2), Tile(2), Tile(2), Tile(2)],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+        ];
+        let mut board =
+            GameBoard::with_cells_and_config(config, Some(cells), 0);
+
+        // a spawn and a move worth of points, then a combo with no spawn
+        assert_eq!(board.r#move(MoveDirection::Left).extra_score, 4);
+        assert_eq!(board.r#move(MoveDirection::Left).extra_score, 9);
+        let cleared = board.clear_row(0, true).unwrap();
+        assert!(cleared >= 8);
+
+        assert_eq!(board.score, 16 + 4 + 9 + cleared);
+        assert_eq!(board.score_breakdown(), &BTreeMap::from([(4, 8), (8, 8)]));
+    }
+
+    #[test]
+    fn bonus_spawns() {
+        let config = GameConfig {
+            bonus_threshold: Some(8),
+            spawn_per_move: 0,
+            ..Default::default()
+        };
+        let mut board = GameBoard::with_cells_and_config(
+            config,
+            Some(SAMPLE_GAME_BOARD),
+            0,
+        );
+        // the bonus tile, which is too small to make another 8
+        board.set_spawn_queue(vec![(None, 2)]);
+
+        // only makes 4s
+        assert_eq!(board.r#move(MoveDirection::Left).bonus_spawns, 0);
+        // makes an 8
+        assert_eq!(board.r#move(MoveDirection::Left).bonus_spawns, 1);
+        assert_eq!(board.r#move(MoveDirection::Left).bonus_spawns, 0);
+    }
+
+    #[test]
+    fn biggest_merge() {
+        let mut board = no_spawns([
+            [Tile(2), Tile(2), Tile(4), Tile(8)],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Tile(64), Vacant, Vacant, Vacant],
+        ]);
+        assert_eq!(board.biggest_merge(), 0);
+
+        board.r#move(MoveDirection::Left);
+        assert_eq!(board.biggest_merge(), 4);
+
+        board.r#move(MoveDirection::Left);
+        board.r#move(MoveDirection::Left);
+        assert_eq!(board.biggest_merge(), 16);
+        assert_eq!(board.highest_tile(), Some(64));
+    }
+
+    #[test]
+    fn move_to_corner() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+
+        let outcome = board.move_to_corner(Corner::TopLeft);
+
+        const EXPECTED: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] = [
+            [Tile(4), Tile(4), Tile(4), Vacant],
+            [Tile(2), Tile(8), Tile(2), Vacant],
+            [Tile(4), Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+        ];
+
+        assert_eq!(board.cells, EXPECTED);
+        assert_eq!(outcome.score_delta, 14);
+        assert_eq!(outcome.merges, 4);
+        assert_eq!(outcome.max_line_merges, 1);
+        assert_eq!(board.score, 14);
+        assert_eq!(board.moves(), 1);
+    }
+
+    #[test]
+    fn just_ended() {
+        let config = GameConfig {
+            win_behavior: WinBehavior::Stop,
+            ..Default::default()
+        };
+        let mut board = GameBoard::with_cells_and_config(
+            config,
+            Some([
+                [Tile(1024), Tile(1024), Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+            ]),
+            0,
+        );
+
+        assert!(!board.is_over());
+        assert!(board.r#move(MoveDirection::Left).just_ended);
+        assert!(board.is_over());
+        assert_eq!(board.state(), GameState::Won);
+
+        let outcome = board.r#move(MoveDirection::Right);
+        assert!(!outcome.moved);
+        assert!(!outcome.just_ended);
+    }
+
+    #[test]
+    fn ever_won() {
+        let mut board = no_spawns([
+            [Tile(2048), Tile(2048), Vacant, Vacant],
+            [Tile(512), Tile(512), Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+        ]);
+        assert!(board.ever_won());
+
+        // the 4096 is past the target too
+        board.r#move(MoveDirection::Left);
+        assert_eq!(board.get((0, 0)), Tile(4096));
+        assert!(board.has_winning_tile());
+        assert!(board.ever_won());
+
+        board.clear_row(0, false);
+        assert!(!board.has_winning_tile());
+        assert!(board.ever_won());
+
+        let mut board = no_spawns([
+            [Tile(1024), Tile(1024), Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+        ]);
+        board.r#move(MoveDirection::Left);
+        assert!(board.ever_won());
+
+        board.undo().unwrap();
+        assert!(!board.has_winning_tile());
+        assert!(!board.ever_won());
+    }
+
+    #[test]
+    fn win_behavior() {
+        let cells = [
+            [Tile(1024), Tile(1024), Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+        ];
+
+        for behavior in [WinBehavior::Prompt, WinBehavior::Continue] {
+            let config = GameConfig {
+                win_behavior: behavior,
+                ..Default::default()
+            };
+            let mut board =
+                GameBoard::with_cells_and_config(config, Some(cells), 0);
+
+            let outcome = board.r#move(MoveDirection::Left);
+            assert_eq!(outcome.just_won, behavior == WinBehavior::Prompt);
+            assert!(!outcome.just_ended);
+            assert!(board.has_won());
+            assert_eq!(board.state(), GameState::Playing);
+
+            assert!(board.r#move(MoveDirection::Right).moved);
+            assert!(!board.r#move(MoveDirection::Left).just_won);
+        }
+    }
+
+    #[test]
+    fn session_best() {
+        let clock = Arc::new(MockClock::default());
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+        board.enable_timing(clock.clone());
+        assert_eq!(board.session_best(), 0);
+
+        // the score only counts once the game is over
+        clock.advance(500);
+        board.r#move(MoveDirection::Left);
+        assert_eq!(board.score, 8);
+        assert_eq!(board.session_best(), 0);
+
+        board.end_game();
+        assert_eq!(board.session_best(), 8);
+
+        let version = board.version();
+        board.restart();
+        assert_eq!(board.score, 0);
+        assert_eq!(board.moves(), 0);
+        assert!(!board.is_over());
+        assert_eq!(board.empty_count(), 14);
+        assert_eq!(board.session_best(), 8);
+        assert!(board.version() > version);
+
+        // the timer keeps running on the same clock from the restart
+        clock.advance(200);
+        assert!(board.r#move(board.legal_moves()[0]).moved);
+        assert_eq!(board.total_play_time(), Some(Duration::from_millis(200)));
+
+        // losing counts as the game being over too
+        let mut board = GameBoard::from([
+            [Tile(2), Tile(2), Tile(16), Tile(8)],
+            [Tile(8), Tile(2), Tile(4), Tile(8)],
+            [Tile(4), Tile(8), Tile(2), Tile(4)],
+            [Tile(8), Tile(4), Tile(8), Tile(2)],
+        ]);
+        board.set_spawn_queue(vec![(Some((3, 0)), 2)]);
+
+        assert!(board.r#move(MoveDirection::Left).just_ended);
+        assert_eq!(board.session_best(), 4);
+
+        // a worse game keeps the best
+        board.session_best = 100;
+        board.restart();
+        assert_eq!(board.session_best(), 100);
+    }
+
+    #[test]
+    fn end_game() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+        assert!(!board.is_over());
+
+        board.end_game();
+        assert!(board.is_over());
+
+        let outcome = board.r#move(MoveDirection::Left);
+        assert!(!outcome.moved);
+        assert!(!outcome.just_ended);
+        assert_eq!(board.cells, SAMPLE_GAME_BOARD);
+    }
+
+    #[test]
+    fn spawns_never_share_a_space() {
+        for seed in 0..200 {
+            let board = GameBoard::with_seed(seed);
+            assert_eq!(
+                board.spaces().filter(|(_, space)| *space != Vacant).count(),
+                STARTING_TILES
+            );
+        }
+
+        // crowded boards, where picking spaces independently would collide
+        // most often
+        let n = GAME_BOARD_SIZE * GAME_BOARD_SIZE;
+        for starting_tiles in [n - 1, n, n + 4] {
+            for seed in 0..20 {
+                let config = GameConfig {
+                    starting_tiles,
+                    seed: Some(seed),
+                    ..Default::default()
+                };
+                let board = GameBoard::with_cells_and_config(config, None, 0);
+                assert_eq!(n - board.empty_count(), starting_tiles.min(n));
+            }
+        }
+
+        let config = GameConfig {
+            spawn_per_move: 13,
+            seed: Some(1),
+            ..Default::default()
+        };
+        let mut cells = [[Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE];
+        cells[0][1] = Tile(2);
+        let mut board =
+            GameBoard::with_cells_and_config(config, Some(cells), 0);
+        board.r#move(MoveDirection::Left);
+        assert_eq!(board.empty_count(), 2);
+        assert_eq!(board.last_spawns.len(), 13);
+
+        // queued spawns aimed at the same space go to different ones
+        let mut board = GameBoard::with_cells_and_config(
+            GameConfig {
+                spawn_per_move: 2,
+                ..Default::default()
+            },
+            Some(cells),
+            0,
+        );
+        board.set_spawn_queue(vec![(Some((3, 3)), 2); 2]);
+        board.r#move(MoveDirection::Left);
+        assert_eq!(board.empty_count(), 13);
+    }
+
+    #[test]
+    fn spawn_queue() {
+        let mut board = GameBoard::from([
+            [Tile(2), Tile(4), Tile(8), Tile(16)],
+            [Tile(4), Tile(8), Tile(16), Tile(32)],
+            [Tile(8), Tile(16), Tile(32), Tile(64)],
+            [Tile(128), Vacant, Vacant, Vacant],
+        ]);
+        board.set_spawn_queue(vec![(Some((3, 3)), 4), (Some((0, 3)), 2)]);
+
+        // both locations are taken by the time they spawn, so the first
+        // vacant space is used instead
+        board.r#move(MoveDirection::Right);
+        assert_eq!(board.cells[3], [Tile(4), Vacant, Vacant, Tile(128)]);
+
+        board.r#move(MoveDirection::Left);
+        assert_eq!(board.cells[3], [Tile(4), Tile(128), Tile(2), Vacant]);
+
+        // the queue is used up, so the next spawn is random
+        let preview = board.preview_cells(MoveDirection::Right);
+        board.r#move(MoveDirection::Right);
+        assert_eq!(preview[3], [Vacant, Tile(4), Tile(128), Tile(2)]);
+        assert_eq!(board.last_spawns.len(), 1);
+        assert_eq!(preview[0], board.cells[0]);
+    }
+
+    #[test]
+    fn reroll_spawn() {
+        let mut board = GameBoard::from(SAMPLE_GAME_BOARD);
+        board.set_spawn_queue(vec![(Some((3, 1)), 2), (Some((0, 2)), 4)]);
+
+        board.r#move(MoveDirection::Left);
+        assert_eq!(board.get((3, 1)), Tile(2));
+        let score = board.score;
+
+        assert!(board.reroll_spawn());
+        assert_eq!(board.get((3, 1)), Vacant);
+        assert_eq!(board.get((0, 2)), Tile(4));
+        assert_eq!(board.score, score);
+        assert!(!board.reroll_spawn());
+
+        let mut board = GameBoard::from([
+            [Tile(2), Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+        ]);
+        board.set_spawn_queue(vec![(Some((2, 0)), 2)]);
+        board.r#move(MoveDirection::Right);
+
+        // a move that changes nothing spawns nothing to reroll
+        assert!(!board.r#move(MoveDirection::Up).moved);
+        assert!(!board.reroll_spawn());
+
+        // the rerolled tile is tracked if it becomes the highest one
+        let mut board = GameBoard::from([
+            [Tile(2), Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+        ]);
+        board.set_spawn_queue(vec![(Some((0, 0)), 2), (Some((0, 3)), 4)]);
+        board.r#move(MoveDirection::Right);
+        assert_eq!(board.max_tile_positions(), [(0, 0)]);
+
+        let version = board.version();
+        assert!(board.reroll_spawn());
+        assert_eq!(board.max_tile_positions(), [(0, 3)]);
+        assert!(board.version() > version);
+    }
+
+    #[test]
+    fn replay_with_spawns() {
+        // record a seeded game's spawns, then play it again from them
+        let mut played = GameBoard::with_seed(11);
+        let mut moves = Vec::new();
+        let mut spawns = played
+            .spaces()
+            .filter_map(|(loc, space)| Some((loc, space.value()?)))
+            .collect::<Vec<_>>();
+
+        for dir in MoveDirection::all().into_iter().cycle().take(40) {
+            played.r#move(dir);
+            moves.push(dir);
+
+            for loc in &played.last_spawns {
+                spawns.push((*loc, played.get(*loc).value().unwrap()));
+            }
+        }
+
+        let replayed = GameBoard::replay_with_spawns(&moves, &spawns).unwrap();
+        assert_eq!(replayed.cells, played.cells);
+        assert_eq!(replayed.score, played.score);
+        assert_eq!(replayed.moves(), played.moves());
+
+        let used = spawns.len();
+        spawns.push(((0, 0), 2));
+        assert_eq!(
+            GameBoard::replay_with_spawns(&moves, &spawns).unwrap_err(),
+            ReplayError::TooManySpawns {
+                used,
+                found: used + 1
+            }
+        );
+
+        assert_eq!(
+            GameBoard::replay_with_spawns(&moves, &spawns[..1]).unwrap_err(),
+            ReplayError::TooFewSpawns {
+                found: 1,
+                moves_replayed: 0
+            }
+        );
+
+        // the second starting tile can't go on top of the first
+        let spawns = [((0, 0), 2), ((0, 0), 2)];
+        assert_eq!(
+            GameBoard::replay_with_spawns(&[], &spawns).unwrap_err(),
+            ReplayError::InvalidSpawn { index: 1 }
+        );
+
+        let spawns = [((0, 0), 2), ((4, 0), 2)];
+        assert_eq!(
+            GameBoard::replay_with_spawns(&[], &spawns).unwrap_err(),
+            ReplayError::InvalidSpawn { index: 1 }
+        );
+    }
+
+    #[test]
+    fn expectimax_move() {
+        // only merges are scored, so this can't see past its next move
+        fn greedy(board: &GameBoard) -> Option<MoveDirection> {
+            board.legal_moves().into_iter().max_by_key(|dir| {
+                let mut cells = board.cells;
+                slide_cells(&mut cells, *dir, board.config()).score_delta
+            })
+        }
+
+        let mut greedy_score = 0;
+        let mut expectimax_score = 0;
+        for seed in 0..3 {
+            let mut board = GameBoard::with_seed(seed);
+            board.simulate(greedy, 2000);
+            greedy_score += board.score;
+
+            let mut board = GameBoard::with_seed(seed);
+            board.simulate(|b| b.expectimax_move(2, 4), 2000);
+            expectimax_score += board.score;
+        }
+
+        assert!(
+            expectimax_score > greedy_score * 2,
+            "{expectimax_score} vs {greedy_score}"
+        );
+
+        let mut board = no_spawns([[Tile(2); 4]; 4]);
+        board.config.allowed_directions = [false, true, false, false];
+        board.set((0, 1), Tile(4));
+        assert_eq!(board.expectimax_move(1, 4), Some(MoveDirection::Up));
+
+        let board = no_spawns(LOST_GAME_BOARD);
+        assert_eq!(board.expectimax_move(3, 4), None);
+    }
+
+    #[test]
+    fn ai_move() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let average_score = |difficulty| {
+            let mut total = 0;
+            for seed in 0..2 {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let mut board = GameBoard::with_seed(seed);
+                board.simulate(|b| b.ai_move(difficulty, &mut rng), 150);
+                total += board.score;
+            }
+
+            total / 2
+        };
+
+        let easy = average_score(Difficulty::Easy);
+        let medium = average_score(Difficulty::Medium);
+        let hard = average_score(Difficulty::Hard);
+        assert!(easy < medium && medium < hard, "{easy} {medium} {hard}");
+
+        let board = no_spawns(LOST_GAME_BOARD);
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(board.ai_move(Difficulty::Easy, &mut rng), None);
+    }
+
+    #[test]
+    fn simulate() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+        let trajectory = board.simulate(|_| Some(MoveDirection::Left), 3);
+
+        assert_eq!(trajectory.initial.cells, SAMPLE_GAME_BOARD);
+        assert_eq!(trajectory.steps.len(), 3);
+        assert!(trajectory.steps[0].outcome.moved);
+        assert!(!trajectory.steps[2].outcome.moved);
+        assert_eq!(trajectory.steps[2].cells, board.cells);
+        assert_eq!(trajectory.state, GameState::Playing);
+        assert_eq!(trajectory.replay().cells, board.cells);
+
+        // spawns are replayed where they landed, even without a seed
+        let mut unseeded = GameBoard::new();
+        let trajectory =
+            unseeded.simulate(|b| b.legal_moves().first().copied(), 30);
+        assert_eq!(trajectory.steps[0].spawns.len(), 1);
+        assert_eq!(trajectory.replay().cells, unseeded.cells);
+
+        let mut dirs = MoveDirection::all().into_iter();
+        let trajectory = board.simulate(|_| dirs.next(), 100);
+        assert_eq!(trajectory.steps.len(), 4);
+
+        let mut board = no_spawns(LOST_GAME_BOARD);
+        let trajectory = board.simulate(|_| Some(MoveDirection::Up), 10);
+        assert!(trajectory.steps.is_empty());
+        assert_eq!(trajectory.state, GameState::Lost);
+    }
+
+    #[test]
+    fn try_move() {
+        let cells = [
+            [Tile(2), Tile(4), Tile(8), Tile(16)],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+        ];
+        let config = GameConfig {
+            seed: Some(5),
+            ..Default::default()
+        };
+        let mut board =
+            GameBoard::with_cells_and_config(config, Some(cells), 0);
+        let rng = board.rng_state();
+
+        assert_eq!(board.try_move(MoveDirection::Up), Err(IllegalMove));
+        assert_eq!(board.cells, cells);
+        assert_eq!(board.moves(), 0);
+        assert_eq!(board.rng_state(), rng);
+
+        assert!(board.try_move(MoveDirection::Down).unwrap().moved);
+        assert_eq!(board.moves(), 1);
+
+        board.end_game();
+        assert_eq!(board.try_move(MoveDirection::Up), Err(IllegalMove));
+    }
+
+    #[test]
+    fn move_with_spawn() {
+        let mut board = GameBoard::from(SAMPLE_GAME_BOARD);
+        let before = board.clone();
+
+        // the sample board's bottom left tile is still there after moving
+        // left
+        assert_eq!(
+            board.move_with_spawn(MoveDirection::Left, ((0, 3), 2)),
+            Err(SpawnError::NotVacant)
+        );
+        assert_eq!(
+            board.move_with_spawn(MoveDirection::Left, ((0, 4), 2)),
+            Err(SpawnError::NotVacant)
+        );
+        assert_eq!(board.cells, before.cells);
+        assert_eq!(board.moves(), 0);
+
+        let outcome = board
+            .move_with_spawn(MoveDirection::Left, ((3, 2), 8))
+            .unwrap();
+        assert_eq!(outcome.score_delta, 8);
+        assert_eq!(board.get((3, 2)), Tile(8));
+        assert_eq!(board.last_spawns, [(3, 2)]);
+        assert_eq!(board.empty_count(), 6);
 
-        for _ in 0..STARTING_TILES {
-            board.add_random_tile();
-        }
+        // nothing can spawn after a move that doesn't change anything
+        let mut cells = [[Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE];
+        cells[0][0] = Tile(2);
+        let mut board = GameBoard::from(cells);
+        assert_eq!(
+            board.move_with_spawn(MoveDirection::Left, ((3, 2), 2)),
+            Err(SpawnError::IllegalMove)
+        );
+        assert_eq!(board.get((3, 2)), Vacant);
+
+        board.end_game();
+        assert_eq!(
+            board.move_with_spawn(MoveDirection::Right, ((0, 2), 2)),
+            Err(SpawnError::IllegalMove)
+        );
 
+        // the tile is placed even when the rules don't spawn any
+        let mut board = no_spawns(cells);
         board
+            .move_with_spawn(MoveDirection::Right, ((0, 2), 4))
+            .unwrap();
+        assert_eq!(board.get((0, 2)), Tile(4));
+        assert_eq!(board.last_spawns, [(0, 2)]);
+        assert_eq!(board.empty_count(), 14);
+        assert!(board.spawn_queue.is_empty());
+
+        board.r#move(MoveDirection::Left);
+        assert_eq!(board.empty_count(), 14);
     }
 
-    /// Creates a new blank square [`GameBoard`](Self) with dimensions
-    /// [`GAME_BOARD_SIZE`]
-    ///
-    /// ```
-    /// use lib_2048::{BoardSpace::*, GameBoard};
-    ///
-    /// assert_eq!(
-    ///     GameBoard::empty().cells,
-    ///     [
-    ///         [Vacant, Vacant, Vacant, Vacant],
-    ///         [Vacant, Vacant, Vacant, Vacant],
-    ///         [Vacant, Vacant, Vacant, Vacant],
-    ///         [Vacant, Vacant, Vacant, Vacant],
-    ///     ]
-    /// );
-    /// ```
-    pub fn empty() -> Self {
-        Self {
-            cells: [[BoardSpace::Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE],
-            score: 0,
-            #[cfg(not(test))]
-            rng: OsRng {},
+    #[test]
+    fn all_legal_successors() {
+        let mut board = GameBoard::with_cells_and_config(
+            GameConfig {
+                seed: Some(8),
+                ..Default::default()
+            },
+            Some(SAMPLE_GAME_BOARD),
+            0,
+        );
+        board.r#move(MoveDirection::Down);
+        let rng = board.rng_state();
+
+        let successors = board.all_legal_successors();
+        let dirs: Vec<_> = successors.iter().map(|(dir, _)| *dir).collect();
+        assert_eq!(dirs, board.legal_moves());
+
+        for (dir, mut successor) in successors {
+            assert_eq!(successor.cells, board.preview_cells(dir));
+            assert_eq!(successor.moves(), board.moves());
+            assert_eq!(successor.rng_state(), rng);
+
+            successor.r#move(MoveDirection::Left);
+            successor.r#move(MoveDirection::Up);
         }
-    }
+        assert_eq!(board.rng_state(), rng);
 
-    /// Gets the value of a cell on the game board
-    pub fn get(&self, (x, y): GameBoardLocation) -> BoardSpace {
-        self.cells[y][x]
-    }
+        board.config.allowed_directions = [true, false, false, false];
+        let successors = board.all_legal_successors();
+        assert!(successors
+            .iter()
+            .all(|(dir, _)| *dir == MoveDirection::Left));
 
-    /// Sets the value of a cell on the game board
-    pub fn set(&mut self, (x, y): GameBoardLocation, val: BoardSpace) {
-        self.cells[y][x] = val;
+        let board = no_spawns(LOST_GAME_BOARD);
+        assert!(board.all_legal_successors().is_empty());
     }
 
-    /// Moves all tiles on the board, merging any adjacent tiles of the same numeric value
-    pub fn r#move(&mut self, dir: MoveDirection) {
-        let rot = dir as usize;
-        self.rotate(rot);
+    #[test]
+    fn combo() {
+        let combo = |window| GameConfig {
+            combo: Some(Combo {
+                window,
+                multiplier: 2,
+            }),
+            ..Default::default()
+        };
 
-        let mut moved = false;
+        let mut board = GameBoard::with_cells_and_config(
+            combo(ComboWindow::Moves(1)),
+            Some([
+                [Tile(2), Tile(2), Vacant, Tile(4)],
+                [Tile(4), Vacant, Vacant, Tile(4)],
+                [Vacant, Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+            ]),
+            0,
+        );
+        board.set_spawn_queue(vec![(Some((3, 3)), 2), (Some((0, 3)), 2)]);
 
-        for y in 0..self.cells.len() {
-            for x in 0..self.cells.len() {
-                if let BoardSpace::Tile(t) = self.cells[y][x] {
-                    for x2 in (x + 1)..self.cells.len() {
-                        match self.cells[y][x2] {
-                            BoardSpace::Tile(t2) if t == t2 => {
-                                let new_val = t * 2;
-                                self.score += new_val;
-
-                                self.cells[y][x] = BoardSpace::Tile(new_val);
-                                self.cells[y][x2] = BoardSpace::Vacant;
-
-                                moved = true;
-                                break;
-                            },
-
-                            BoardSpace::Tile(_) => break,
-                            _ => continue,
-                        }
-                    }
-                }
-            }
+        // the first merge only opens the window
+        let outcome = board.r#move(MoveDirection::Left);
+        assert!(!outcome.combo);
+        assert_eq!(board.get((3, 3)), Tile(2));
 
-            for x in 0..self.cells.len() {
-                if let BoardSpace::Vacant = self.cells[y][x] {
-                    for x2 in x..self.cells.len() {
-                        if let BoardSpace::Tile(_) = self.cells[y][x2] {
-                            self.cells[y].swap(x, x2);
+        // merging again straight away doubles the points and spawns nothing
+        let outcome = board.r#move(MoveDirection::Left);
+        assert!(outcome.combo);
+        assert_eq!((outcome.score_delta, outcome.extra_score), (8, 8));
+        assert_eq!(board.score, 12 + 16);
+        assert_eq!(board.empty_count(), 13);
 
-                            moved = true;
-                            break;
-                        }
-                    }
-                }
-            }
-        }
+        board.undo().unwrap();
+        assert!(board.r#move(MoveDirection::Left).combo);
 
-        self.rotate(self.cells.len() - rot);
+        assert!(board.r#move(MoveDirection::Up).combo);
+        assert_eq!(board.get((0, 0)), Tile(16));
+        assert_eq!(board.empty_count(), 14);
 
-        if moved {
-            self.add_random_tile();
+        // the move straight after a merge is a combo even if it doesn't
+        // merge anything
+        let outcome = board.r#move(MoveDirection::Right);
+        assert!(outcome.combo);
+        assert_eq!(outcome.extra_score, 0);
+        assert_eq!(board.empty_count(), 14);
+
+        // and once the window has passed, tiles spawn as usual
+        let outcome = board.r#move(MoveDirection::Left);
+        assert!(!outcome.combo);
+        assert_eq!(board.get((0, 3)), Tile(2));
+
+        // the window counts moves that don't merge
+        for (window, expected) in [(1, false), (2, true)] {
+            let mut board = GameBoard::with_cells_and_config(
+                GameConfig {
+                    spawn_per_move: 0,
+                    ..combo(ComboWindow::Moves(window))
+                },
+                Some([
+                    [Tile(2), Tile(2), Vacant, Vacant],
+                    [Tile(4), Vacant, Vacant, Vacant],
+                    [Vacant, Vacant, Vacant, Vacant],
+                    [Vacant, Vacant, Vacant, Vacant],
+                ]),
+                0,
+            );
+            board.r#move(MoveDirection::Left);
+            board.r#move(MoveDirection::Right);
+            assert_eq!(board.r#move(MoveDirection::Up).combo, expected);
         }
+
+        let clock = Arc::new(MockClock::default());
+        let mut board = GameBoard::with_cells_and_config(
+            GameConfig {
+                spawn_per_move: 0,
+                ..combo(ComboWindow::Time(Duration::from_millis(500)))
+            },
+            Some([
+                [Tile(2), Tile(2), Tile(2), Tile(2)],
+                [Tile(8), Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+            ]),
+            0,
+        );
+
+        // without a clock, nothing is a combo
+        board.r#move(MoveDirection::Left);
+        assert!(!board.r#move(MoveDirection::Left).combo);
+
+        board.undo().unwrap();
+        board.undo().unwrap();
+        board.enable_timing(clock.clone());
+        board.r#move(MoveDirection::Left);
+        clock.advance(400);
+        assert!(board.r#move(MoveDirection::Left).combo);
+        clock.advance(600);
+        assert!(!board.r#move(MoveDirection::Up).combo);
+        assert_eq!(board.get((0, 0)), Tile(16));
     }
 
-    pub fn has_lost(&self) -> bool {
-        !self.can_move()
+    #[test]
+    fn decay() {
+        let decay = |after_moves, floor| GameConfig {
+            decay: Some(Decay { after_moves, floor }),
+            spawn_per_move: 0,
+            ..Default::default()
+        };
+
+        let mut board = GameBoard::with_cells_and_config(
+            decay(2, DecayFloor::Vanish),
+            Some([
+                [Tile(2), Tile(2), Vacant, Vacant],
+                [Tile(8), Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+            ]),
+            0,
+        );
+        assert_eq!(board.tile_age((0, 1)), Some(0));
+        assert_eq!(board.tile_age((1, 1)), None);
+
+        // merging starts a tile's age again
+        board.r#move(MoveDirection::Right);
+        assert_eq!(board.tile_age((3, 0)), Some(0));
+        assert_eq!(board.tile_age((3, 1)), Some(1));
+
+        // moves that change nothing don't age anything
+        board.r#move(MoveDirection::Right);
+        assert_eq!(board.tile_age((3, 1)), Some(1));
+
+        let outcome = board.r#move(MoveDirection::Left);
+        assert_eq!(outcome.decayed, 1);
+        assert_eq!(board.get((0, 0)), Tile(4));
+        assert_eq!(board.get((0, 1)), Tile(4));
+        assert_eq!(board.tile_age((0, 0)), Some(1));
+        assert_eq!(board.tile_age((0, 1)), Some(0));
+
+        board.undo().unwrap();
+        assert_eq!(board.get((3, 1)), Tile(8));
+        assert_eq!(board.tile_age((3, 1)), Some(1));
+
+        // tiles never decay below the smallest tile that can spawn
+        let mut board = GameBoard::with_cells_and_config(
+            decay(1, DecayFloor::Keep),
+            Some([
+                [Tile(2), Vacant, Vacant, Vacant],
+                [Tile(4), Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+            ]),
+            0,
+        );
+        assert_eq!(board.r#move(MoveDirection::Right).decayed, 2);
+        assert_eq!(board.get((3, 0)), Tile(2));
+        assert_eq!(board.get((3, 1)), Tile(2));
+
+        let mut board = GameBoard::with_cells_and_config(
+            decay(1, DecayFloor::Vanish),
+            Some([
+                [Tile(2), Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+            ]),
+            0,
+        );
+        board.r#move(MoveDirection::Right);
+        assert_eq!(board.empty_count(), 16);
+
+        // a tile that merges around the edge takes the last tile with it
+        let mut board = GameBoard::with_cells_and_config(
+            GameConfig {
+                wrap: true,
+                ..decay(5, DecayFloor::Vanish)
+            },
+            Some([
+                [Tile(2), Tile(4), Tile(8), Tile(2)],
+                [Vacant, Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+            ]),
+            0,
+        );
+        board.r#move(MoveDirection::Left);
+        assert_eq!(board.cells[0], [Tile(4), Tile(4), Tile(8), Vacant]);
+        assert_eq!(board.tile_age((0, 0)), Some(0));
+        assert_eq!(board.tile_age((1, 0)), Some(1));
+        assert_eq!(board.tile_age((2, 0)), Some(1));
+
+        // without decay, ages aren't counted
+        let board = no_spawns(SAMPLE_GAME_BOARD);
+        assert_eq!(board.tile_age((0, 0)), None);
+        assert_eq!(board.moves_until_decay((0, 0)), None);
     }
 
-    // TODO: make this not mutate?
-    fn can_move(&self) -> bool {
-        // TODO: ugly hack, is there a better way to do this?
-        let mut cells = self.cells.clone();
-
-        for n in 0..4 {
-            rotate(&mut cells, n);
-
-            for y in 0..cells.len() {
-                for x in 0..(cells.len() - 1) {
-                    match cells[y][x] {
-                        BoardSpace::Vacant => {
-                            rotate(&mut cells, 4 - n);
-                            return true;
-                        },
-
-                        BoardSpace::Tile(t) => match cells[y][x + 1] {
-                            BoardSpace::Vacant => {
-                                rotate(&mut cells, 4 - n);
-                                return true;
-                            },
-
-                            BoardSpace::Tile(t2) if t == t2 => {
-                                rotate(&mut cells, 4 - n);
-                                return true;
-                            },
-
-                            _ => continue,
-                        },
-                    }
-                }
-            }
-        }
+    #[test]
+    fn move_checked() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+        let (outcome, state) = board.move_checked(MoveDirection::Left);
+        assert!(outcome.moved);
+        assert_eq!(state, GameState::Playing);
+        assert_eq!(state, board.state());
+
+        // moves that don't change the board still report the state
+        board.move_checked(MoveDirection::Left);
+        let (outcome, state) = board.move_checked(MoveDirection::Left);
+        assert!(!outcome.moved);
+        assert_eq!(state, GameState::Playing);
 
-        false
+        board.end_game();
+        let (outcome, state) = board.move_checked(MoveDirection::Right);
+        assert!(!outcome.moved);
+        assert_eq!(state, GameState::Lost);
+
+        let config = GameConfig {
+            move_limit: Some(1),
+            spawn_per_move: 0,
+            ..Default::default()
+        };
+        let mut board = GameBoard::with_cells_and_config(
+            config,
+            Some(SAMPLE_GAME_BOARD),
+            0,
+        );
+        let (outcome, state) = board.move_checked(MoveDirection::Left);
+        assert!(outcome.just_ended);
+        assert_eq!(state, GameState::LimitReached);
     }
 
-    fn rotate(&mut self, times: usize) {
-        rotate(&mut self.cells, times);
+    #[test]
+    fn with_handicap() {
+        let board =
+            GameBoard::with_handicap(&[((1, 0), 64), ((2, 3), 4)]).unwrap();
+        assert_eq!(board.get((1, 0)), Tile(64));
+        assert_eq!(board.get((2, 3)), Tile(4));
+        assert_eq!(board.empty_count(), 14);
+        assert_eq!(board.score, 0);
+        assert!(board.can_move());
+
+        assert_eq!(
+            GameBoard::with_handicap(&[((1, 0), 64), ((2, 3), 4), ((1, 0), 2)])
+                .unwrap_err(),
+            HandicapError::Overlapping { index: 2 }
+        );
+        assert_eq!(
+            GameBoard::with_handicap(&[((0, 4), 64)]).unwrap_err(),
+            HandicapError::OutOfBounds { index: 0 }
+        );
+        assert_eq!(
+            GameBoard::with_handicap(&[((0, 0), 2), ((0, 1), 0)]).unwrap_err(),
+            HandicapError::InvalidTile { index: 1 }
+        );
+
+        // a full board with no neighbors that merge can't be played
+        let full = (0..GAME_BOARD_SIZE)
+            .flat_map(|y| (0..GAME_BOARD_SIZE).map(move |x| (x, y)))
+            .map(|(x, y)| ((x, y), if (x + y) % 2 == 0 { 2 } else { 4 }))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            GameBoard::with_handicap(&full).unwrap_err(),
+            HandicapError::NotPlayable
+        );
+        assert_eq!(
+            GameBoard::with_handicap(&[]).unwrap_err(),
+            HandicapError::NotPlayable
+        );
     }
 
-    fn all_empty_spaces(&self) -> Vec<GameBoardLocation> {
-        let mut locations: Vec<GameBoardLocation> = vec![];
+    #[test]
+    fn next_scripted_direction() {
+        let config = GameConfig {
+            direction_cycle: vec![MoveDirection::Down, MoveDirection::Left],
+            spawn_per_move: 0,
+            ..Default::default()
+        };
+        let mut board = GameBoard::with_cells_and_config(
+            config,
+            Some([
+                [Vacant, Tile(2), Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+            ]),
+            0,
+        );
 
-        for y in 0..self.cells.len() {
-            for x in 0..self.cells.len() {
-                if let BoardSpace::Vacant = self.cells[y][x] {
-                    locations.push((x, y));
-                }
-            }
-        }
+        let dir = board.next_scripted_direction();
+        assert_eq!(dir, MoveDirection::Down);
+        board.r#move(dir);
 
-        locations
+        let dir = board.next_scripted_direction();
+        assert_eq!(dir, MoveDirection::Left);
+        board.r#move(dir);
+
+        // nothing in the cycle can move the tile out of the corner
+        assert_eq!(board.next_scripted_direction(), MoveDirection::Down);
+        assert_eq!(board.next_scripted_direction(), MoveDirection::Left);
     }
 
-    #[cfg(test)]
-    fn add_random_tile(&mut self) {}
+    #[test]
+    fn undo() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+        assert_eq!(board.undos_remaining(), None);
 
-    #[cfg(not(test))]
-    fn add_random_tile(&mut self) {
-        let free_spaces = self.all_empty_spaces();
+        board.r#move(MoveDirection::Left);
+        board.r#move(MoveDirection::Up);
+        let version = board.version();
 
-        if !free_spaces.is_empty() {
-            let tile = if self.rng.gen_bool(1.0 / 10.0) {
-                BoardSpace::Tile(4)
-            } else {
-                BoardSpace::Tile(2)
-            };
+        assert_eq!(board.undo(), Ok(()));
+        assert_eq!(board.moves(), 1);
+        assert!(board.version() > version);
 
-            let pos = free_spaces
-                .choose(&mut self.rng)
-                .expect("failed to choose random position");
+        assert_eq!(board.undo(), Ok(()));
+        assert_eq!(board.cells, SAMPLE_GAME_BOARD);
+        assert_eq!(board.score, 0);
+        assert_eq!(board.moves(), 0);
+        assert!(board.score_breakdown().is_empty());
+        assert_eq!(board.biggest_merge(), 0);
 
-            self.set(*pos, tile);
-        }
+        assert_eq!(board.undo(), Err(UndoError::NothingToUndo));
+
+        // moves that change nothing can't be undone
+        let mut board = no_spawns([
+            [Tile(2), Tile(4), Tile(8), Tile(16)],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+            [Vacant, Vacant, Vacant, Vacant],
+        ]);
+        board.r#move(MoveDirection::Up);
+        assert_eq!(board.undo(), Err(UndoError::NothingToUndo));
     }
-}
 
-impl Default for GameBoard {
-    fn default() -> Self {
-        Self::empty()
+    #[test]
+    fn max_tile_positions() {
+        let mut board = no_spawns([[Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE]);
+        board.set((3, 0), Tile(4));
+        board.set((0, 3), Tile(4));
+
+        // ties go to the first tile in row-major order
+        board.r#move(MoveDirection::Left);
+        assert_eq!(board.max_tile_positions(), [(0, 0)]);
+
+        // moves that change nothing aren't recorded
+        board.r#move(MoveDirection::Left);
+        assert_eq!(board.max_tile_positions(), [(0, 0)]);
+
+        board.r#move(MoveDirection::Down);
+        assert_eq!(board.max_tile_positions(), [(0, 0), (0, 3)]);
+
+        board.undo().unwrap();
+        assert_eq!(board.max_tile_positions(), [(0, 0)]);
     }
-}
 
-impl From<[[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE]> for GameBoard {
-    fn from(def: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE]) -> Self {
-        Self {
-            cells: def,
+    #[test]
+    fn undo_budget() {
+        let config = GameConfig {
+            undos: Some(1),
             ..Default::default()
-        }
-    }
-}
+        };
+        let mut board = GameBoard::with_cells_and_config(
+            config,
+            Some(SAMPLE_GAME_BOARD),
+            0,
+        );
 
-/// The direction of a [`move`](GameBoard::move)
-#[derive(Debug)]
-pub enum MoveDirection {
-    Left = 0,
-    Up = 1,
-    Right = 2,
-    Down = 3,
-}
+        // failing for lack of history doesn't use up an undo
+        assert_eq!(board.undo(), Err(UndoError::NothingToUndo));
+        assert_eq!(board.undos_remaining(), Some(1));
 
-/// A space on the [`GameBoard`]
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum BoardSpace {
-    /// An empty space
-    Vacant,
-    /// A tile with a numeric value
-    Tile(usize),
-}
+        board.r#move(MoveDirection::Left);
+        board.r#move(MoveDirection::Up);
+        assert_eq!(board.undo(), Ok(()));
+        assert_eq!(board.undos_remaining(), Some(0));
 
-impl fmt::Display for BoardSpace {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Vacant => write!(f, ""),
-            Self::Tile(n) => write!(f, "{n}"),
-        }
+        assert_eq!(board.undo(), Err(UndoError::NoUndosLeft));
+        assert_eq!(board.moves(), 1);
     }
-}
 
-fn rotate(
-    arrs: &mut [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE],
-    times: usize,
-) {
-    let n = arrs.len();
+    #[test]
+    fn apply_move_str() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+        let mut expected = no_spawns(SAMPLE_GAME_BOARD);
+        for dir in [
+            MoveDirection::Up,
+            MoveDirection::Up,
+            MoveDirection::Left,
+            MoveDirection::Right,
+        ] {
+            expected.r#move(dir);
+        }
+
+        let outcomes = board.apply_move_str(" uU\tl r ").unwrap();
+        assert_eq!(outcomes.len(), 4);
+        assert_eq!(board.cells, expected.cells);
+        assert_eq!(board.score, expected.score);
 
-    // credit: someone on stackoverflow idk
-    for _ in 0..times {
-        for i in 0..(n / 2) {
-            for j in i..(n - i - 1) {
-                let tmp = arrs[i][j];
-                arrs[i][j] = arrs[j][n - i - 1];
-                arrs[j][n - i - 1] = arrs[n - i - 1][n - j - 1];
-                arrs[n - i - 1][n - j - 1] = arrs[n - j - 1][i];
-                arrs[n - j - 1][i] = tmp;
+        let err = board.apply_move_str("ud lx").unwrap_err();
+        assert_eq!(
+            err,
+            ParseMoveError {
+                position: 4,
+                found: "x".to_string(),
             }
-        }
+        );
+        assert_eq!(board.cells, expected.cells);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::{BoardSpace::*, *};
+    #[test]
+    fn spawn_distribution() {
+        let config = GameConfig {
+            spawn_distribution: vec![(2, 2.0), (4, 1.0), (8, 1.0)],
+            ..Default::default()
+        };
+        let mut board = GameBoard::with_cells_and_config(config, None, 0);
+        assert_eq!(
+            board.spawn_distribution(),
+            [(2, 0.5), (4, 0.25), (8, 0.25)]
+        );
 
-    const SAMPLE_GAME_BOARD: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] = [
-        [Tile(2), Tile(2), Tile(2), Tile(2)],
-        [Tile(2), Tile(8), Tile(1), Vacant],
-        [Vacant, Vacant, Vacant, Vacant],
-        [Tile(2), Tile(4), Tile(1), Tile(2)],
-    ];
+        board.set_spawn_distribution(vec![(2, 0.3), (4, 0.3), (8, 0.3)]);
+        let total: f64 =
+            board.spawn_distribution().iter().map(|(_, p)| p).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
 
     #[test]
-    fn move_up() {
-        let mut board = GameBoard::from(SAMPLE_GAME_BOARD);
+    fn stage_move() {
+        let config = GameConfig {
+            seed: Some(11),
+            ..Default::default()
+        };
+        let mut board = GameBoard::with_cells_and_config(
+            config,
+            Some(SAMPLE_GAME_BOARD),
+            0,
+        );
+        board.set_spawn_queue(vec![(None, 4)]);
+        let rng = board.rng_state();
 
-        board.r#move(MoveDirection::Up);
+        let outcome = board.stage_move(MoveDirection::Left).cloned().unwrap();
+        let preview = board.staged().unwrap().clone();
+        assert_eq!(board.cells, SAMPLE_GAME_BOARD);
+        assert_eq!(board.moves(), 0);
+        assert_eq!(board.rng_state(), rng);
 
-        const EXPECTED: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] = [
-            [Tile(4), Tile(2), Tile(2), Tile(4)],
-            [Tile(2), Tile(8), Tile(2), Vacant],
-            [Vacant, Tile(4), Vacant, Vacant],
-            [Vacant, Vacant, Vacant, Vacant],
-        ];
+        board.cancel_staged();
+        assert!(board.staged().is_none());
+        assert_eq!(board.commit_staged(), None);
+        assert_eq!(board.cells, SAMPLE_GAME_BOARD);
 
-        assert_eq!(board.cells, EXPECTED);
+        board.stage_move(MoveDirection::Left);
+        assert_eq!(board.commit_staged(), Some(outcome));
+        assert_eq!(board.cells, preview.cells);
+        assert_eq!(board.score, preview.score);
+        assert_eq!(board.rng_state(), preview.rng_state());
+        assert_eq!(board.get((2, 0)), Tile(4));
     }
 
     #[test]
-    fn move_down() {
-        let mut board = GameBoard::from(SAMPLE_GAME_BOARD);
+    fn state() {
+        let config = GameConfig {
+            move_limit: Some(1),
+            ..Default::default()
+        };
+        let mut board = GameBoard::with_cells_and_config(
+            config,
+            Some([
+                [Tile(1024), Tile(1024), Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+            ]),
+            0,
+        );
+        assert_eq!(board.state(), GameState::Playing);
 
-        board.r#move(MoveDirection::Down);
+        assert!(board.r#move(MoveDirection::Left).just_ended);
+        assert!(board.has_won());
+        assert_eq!(board.state(), GameState::LimitReached);
 
-        const EXPECTED: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] = [
-            [Vacant, Vacant, Vacant, Vacant],
-            [Vacant, Tile(2), Vacant, Vacant],
-            [Tile(2), Tile(8), Tile(2), Vacant],
-            [Tile(4), Tile(4), Tile(2), Tile(4)],
-        ];
+        assert!(!board.r#move(MoveDirection::Right).moved);
 
-        assert_eq!(board.cells, EXPECTED);
+        board.end_game();
+        assert_eq!(board.state(), GameState::Lost);
     }
 
     #[test]
-    fn move_left() {
-        let mut board = GameBoard::from(SAMPLE_GAME_BOARD);
+    fn puzzle_from_seed() {
+        for seed in 0..20 {
+            let board = GameBoard::puzzle_from_seed(seed, 1.0);
+            assert_eq!(board.empty_count(), 0);
+            assert!(board.can_move());
+            assert_eq!(
+                board.cells,
+                GameBoard::puzzle_from_seed(seed, 1.0).cells
+            );
+        }
 
-        board.r#move(MoveDirection::Left);
+        assert_eq!(GameBoard::puzzle_from_seed(1, 0.0).empty_count(), 15);
+        assert_eq!(GameBoard::puzzle_from_seed(1, 0.5).empty_count(), 8);
+    }
 
-        const EXPECTED: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] = [
-            [Tile(4), Tile(4), Vacant, Vacant],
-            [Tile(2), Tile(8), Tile(1), Vacant],
-            [Vacant, Vacant, Vacant, Vacant],
-            [Tile(2), Tile(4), Tile(1), Tile(2)],
-        ];
+    #[test]
+    fn fullness() {
+        assert_eq!(
+            no_spawns([[Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE]).fullness(),
+            0.0
+        );
+        assert_eq!(no_spawns(SAMPLE_GAME_BOARD).fullness(), 0.6875);
+        assert_eq!(no_spawns([[Tile(2); 4]; 4]).fullness(), 1.0);
+    }
 
-        assert_eq!(board.cells, EXPECTED);
+    #[test]
+    fn largest_empty_region() {
+        let board = no_spawns([
+            [Vacant, Vacant, Tile(2), Vacant],
+            [Vacant, Tile(4), Tile(2), Vacant],
+            [Tile(8), Tile(2), Tile(4), Vacant],
+            [Vacant, Tile(4), Vacant, Vacant],
+        ]);
+        assert_eq!(board.largest_empty_region(), 5);
+
+        assert_eq!(
+            no_spawns([[Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE])
+                .largest_empty_region(),
+            16
+        );
+        assert_eq!(no_spawns([[Tile(2); 4]; 4]).largest_empty_region(), 0);
     }
 
     #[test]
-    fn move_right() {
-        let mut board = GameBoard::from(SAMPLE_GAME_BOARD);
+    fn distinct_tile_values() {
+        let board = no_spawns(SAMPLE_GAME_BOARD);
+        assert_eq!(board.distinct_tile_values(), 4);
 
-        board.r#move(MoveDirection::Right);
+        assert_eq!(
+            no_spawns([[Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE])
+                .distinct_tile_values(),
+            0
+        );
+    }
 
-        const EXPECTED: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] = [
-            [Vacant, Vacant, Tile(4), Tile(4)],
-            [Vacant, Tile(2), Tile(8), Tile(1)],
-            [Vacant, Vacant, Vacant, Vacant],
-            [Tile(2), Tile(4), Tile(1), Tile(2)],
-        ];
+    #[test]
+    fn tiles_sorted_desc() {
+        let board = no_spawns(SAMPLE_GAME_BOARD);
+        assert_eq!(
+            board.tiles_sorted_desc(),
+            [8, 4, 2, 2, 2, 2, 2, 2, 2, 1, 1]
+        );
 
-        assert_eq!(board.cells, EXPECTED);
+        assert!(no_spawns([[Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE])
+            .tiles_sorted_desc()
+            .is_empty());
     }
 
     #[test]
-    fn get() {
-        let board = GameBoard::from(SAMPLE_GAME_BOARD);
-        let got = board.get((0, 0));
-        assert_eq!(got, BoardSpace::Tile(2));
+    fn allowed_directions() {
+        let config = GameConfig {
+            allowed_directions: [true, true, false, false],
+            spawn_per_move: 0,
+            ..Default::default()
+        };
+        let mut board = GameBoard::with_cells_and_config(
+            config,
+            Some([
+                [Vacant, Vacant, Vacant, Tile(2)],
+                [Vacant, Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+                [Vacant, Vacant, Vacant, Vacant],
+            ]),
+            0,
+        );
+
+        assert!(!board.r#move(MoveDirection::Down).moved);
+        assert_eq!(board.legal_moves(), [MoveDirection::Left]);
+        assert!(!board.has_lost());
+
+        assert!(board.move_to_corner(Corner::BottomLeft).moved);
+        assert_eq!(board.get((0, 0)), Tile(2));
+
+        // only moving right or down would do anything now
+        assert!(board.has_lost());
+        assert_eq!(board.state(), GameState::Lost);
     }
 
     #[test]
-    fn set() {
-        let mut board = GameBoard::from(SAMPLE_GAME_BOARD);
-        board.set((0, 0), BoardSpace::Tile(2048));
+    fn has_lost() {
+        let mut cells = LOST_GAME_BOARD;
+        assert!(no_spawns(cells).has_lost());
 
-        const EXPECTED: [[BoardSpace; GAME_BOARD_SIZE]; GAME_BOARD_SIZE] = [
-            [Tile(2048), Tile(2), Tile(2), Tile(2)],
-            [Tile(2), Tile(8), Tile(1), Vacant],
-            [Vacant, Vacant, Vacant, Vacant],
-            [Tile(2), Tile(4), Tile(1), Tile(2)],
-        ];
+        cells[3][3] = Tile(4);
+        assert!(!no_spawns(cells).has_lost());
 
-        assert_eq!(board.cells, EXPECTED);
+        cells[3][3] = Vacant;
+        assert!(!no_spawns(cells).has_lost());
+
+        // nothing can slide on a board with no tiles
+        let empty = no_spawns([[Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE]);
+        assert!(empty.has_lost());
+        assert_eq!(empty.state(), GameState::Lost);
     }
 
     #[test]
-    fn all_empty_spaces() {
-        let board = GameBoard::from(SAMPLE_GAME_BOARD);
+    fn describe() {
+        let mut board = no_spawns(SAMPLE_GAME_BOARD);
+        board.r#move(MoveDirection::Left);
 
-        let expected = vec![(3, 1), (0, 2), (1, 2), (2, 2), (3, 2)];
+        let summary = board.describe();
+        assert_eq!(
+            summary,
+            BoardSummary {
+                highest_tile: Some(8),
+                empty_count: 7,
+                score: 8,
+                moves: 1,
+                is_won: false,
+                is_lost: false,
+                legal_moves: vec![
+                    MoveDirection::Left,
+                    MoveDirection::Up,
+                    MoveDirection::Right,
+                    MoveDirection::Down,
+                ],
+            }
+        );
+        assert_eq!(summary.is_lost, board.has_lost());
+    }
 
-        let got = board.all_empty_spaces();
-        assert_eq!(got, expected);
+    #[test]
+    fn describe_lost() {
+        let mut cells = LOST_GAME_BOARD;
+        cells[3][3] = Tile(2048);
+        let board = no_spawns(cells);
+
+        let summary = board.describe();
+        assert!(summary.is_lost);
+        assert!(summary.is_won);
+        assert!(summary.legal_moves.is_empty());
+        assert_eq!(summary.is_lost, board.has_lost());
     }
 }
@@ -0,0 +1,13 @@
+//! The types most programs using this crate need
+//!
+//! ```
+//! use lib_2048::prelude::*;
+//!
+//! let mut board = GameBoard::new();
+//! board.r#move(MoveDirection::Left);
+//! ```
+
+pub use crate::{
+    BoardSpace, GameBoard, GameState, MoveDirection, MoveOutcome,
+    GAME_BOARD_SIZE,
+};
@@ -0,0 +1,221 @@
+//! Drawing a [`GameBoard`] as an image
+//!
+//! Boards can be drawn as SVG with [`GameBoard::to_svg`], or as raw RGBA
+//! pixels with [`GameBoard::to_rgba`] for encoding as PNG or GIF frames.
+//!
+//! Tiles use the colors from the original game. Each tile is [`TILE_SIZE`]
+//! pixels wide with a [`GAP`] between them, so the image grows with the
+//! board. Tiles that will [decay](crate::GameConfig::decay) on the next move
+//! are faded halfway into the board's color.
+
+use std::fmt::Write;
+
+use crate::{BoardSpace, GameBoard, GameBoardLocation};
+
+/// The width and height of a tile, in pixels
+pub const TILE_SIZE: usize = 100;
+/// The space around and between tiles, in pixels
+pub const GAP: usize = 12;
+
+const BOARD_COLOR: &str = "#bbada0";
+const DARK_TEXT: &str = "#776e65";
+const LIGHT_TEXT: &str = "#f9f6f2";
+
+/// The digits 0-9 as 3x5 pixel glyphs, one row of three bits per entry, used
+/// by [`GameBoard::to_rgba`]
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+/// Gets the background color of a space
+fn tile_color(space: BoardSpace) -> &'static str {
+    match space {
+        BoardSpace::Vacant => "#cdc1b4",
+        BoardSpace::Tile(t) => match t {
+            0..=2 => "#eee4da",
+            3..=4 => "#ede0c8",
+            5..=8 => "#f2b179",
+            9..=16 => "#f59563",
+            17..=32 => "#f67c5f",
+            33..=64 => "#f65e3b",
+            65..=128 => "#edcf72",
+            129..=256 => "#edcc61",
+            257..=512 => "#edc850",
+            513..=1024 => "#edc53f",
+            1025..=2048 => "#edc22e",
+            _ => "#3c3a32",
+        },
+    }
+}
+
+impl GameBoard {
+    /// Gets the background color of the space at `loc`, faded if its tile
+    /// is about to decay
+    fn space_color(&self, loc: GameBoardLocation) -> String {
+        let color = tile_color(self.get(loc));
+        if self.moves_until_decay(loc) != Some(1) {
+            return color.to_string();
+        }
+
+        let [r, g, b] = rgb(color);
+        let [r2, g2, b2] = rgb(BOARD_COLOR);
+        let mix = |a: u8, b: u8| ((a as u16 + b as u16) / 2) as u8;
+        format!("#{:02x}{:02x}{:02x}", mix(r, r2), mix(g, g2), mix(b, b2))
+    }
+
+    /// Draws the board as an SVG image
+    ///
+    /// Tile values are centered in their tiles, and shrink to fit as they
+    /// get longer.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard};
+    ///
+    /// let mut board = GameBoard::empty();
+    /// board.set((0, 0), Tile(2048));
+    ///
+    /// let svg = board.to_svg();
+    /// assert!(svg.starts_with("<svg"));
+    /// assert!(svg.contains(">2048</text>"));
+    /// ```
+    pub fn to_svg(&self) -> String {
+        let size = self.image_size();
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" \
+             height=\"{size}\" viewBox=\"0 0 {size} {size}\">\
+             <rect width=\"{size}\" height=\"{size}\" rx=\"6\" \
+             fill=\"{BOARD_COLOR}\"/>"
+        );
+
+        for (y, row) in self.rows().enumerate() {
+            for (x, space) in row.iter().enumerate() {
+                let left = GAP + x * (TILE_SIZE + GAP);
+                let top = GAP + y * (TILE_SIZE + GAP);
+
+                write!(
+                    svg,
+                    "<rect x=\"{left}\" y=\"{top}\" width=\"{TILE_SIZE}\" \
+                     height=\"{TILE_SIZE}\" rx=\"3\" fill=\"{}\"/>",
+                    self.space_color((x, y)),
+                )
+                .unwrap();
+
+                if let BoardSpace::Tile(t) = space {
+                    let text = t.to_string();
+                    let color = if *t <= 4 { DARK_TEXT } else { LIGHT_TEXT };
+                    // digits are about 0.6 times as wide as they are tall, so
+                    // this keeps the text within 80% of the tile's width
+                    let font_size =
+                        (TILE_SIZE / 2).min(TILE_SIZE * 4 / 3 / text.len());
+
+                    write!(
+                        svg,
+                        "<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" \
+                         font-weight=\"bold\" font-size=\"{font_size}\" \
+                         fill=\"{color}\" text-anchor=\"middle\" \
+                         dominant-baseline=\"central\">{text}</text>",
+                        left + TILE_SIZE / 2,
+                        top + TILE_SIZE / 2,
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
+        svg += "</svg>";
+        svg
+    }
+
+    /// Gets the width and height of the images drawn by
+    /// [`to_svg`](Self::to_svg) and [`to_rgba`](Self::to_rgba), in pixels
+    pub fn image_size(&self) -> usize {
+        let n = self.size();
+        n * TILE_SIZE + (n + 1) * GAP
+    }
+
+    /// Draws the board as RGBA pixels, row by row from the top left, in a
+    /// square [`image_size`](Self::image_size) pixels wide
+    ///
+    /// This is the same picture as [`to_svg`](Self::to_svg), with square
+    /// corners and tile values drawn in a blocky pixel font.
+    ///
+    /// ```
+    /// use lib_2048::GameBoard;
+    ///
+    /// let board = GameBoard::new();
+    /// let size = board.image_size();
+    ///
+    /// assert_eq!(board.to_rgba().len(), size * size * 4);
+    /// ```
+    pub fn to_rgba(&self) -> Vec<u8> {
+        let size = self.image_size();
+        let mut pixels = Vec::with_capacity(size * size * 4);
+        for _ in 0..size * size {
+            pixels.extend(rgb(BOARD_COLOR));
+            pixels.push(u8::MAX);
+        }
+
+        let mut fill =
+            |left: usize, top: usize, w: usize, h: usize, color: &str| {
+                for y in top..top + h {
+                    for x in left..left + w {
+                        let i = (y * size + x) * 4;
+                        pixels[i..i + 3].copy_from_slice(&rgb(color));
+                    }
+                }
+            };
+
+        for ((x, y), space) in self.spaces() {
+            let left = GAP + x * (TILE_SIZE + GAP);
+            let top = GAP + y * (TILE_SIZE + GAP);
+            fill(left, top, TILE_SIZE, TILE_SIZE, &self.space_color((x, y)));
+
+            if let BoardSpace::Tile(t) = space {
+                let text = t.to_string();
+                let color = if t <= 4 { DARK_TEXT } else { LIGHT_TEXT };
+
+                // each glyph is 3 pixels wide with 1 pixel between them, and
+                // is scaled up to fill at most 80% of the tile's width
+                let columns = text.len() * 4 - 1;
+                let scale = (TILE_SIZE * 4 / 5 / columns).clamp(1, 8);
+                let text_left = left + (TILE_SIZE - columns * scale) / 2;
+                let text_top = top + (TILE_SIZE - 5 * scale) / 2;
+
+                for (i, digit) in text.bytes().enumerate() {
+                    let glyph = DIGITS[(digit - b'0') as usize];
+                    for (gy, bits) in glyph.iter().enumerate() {
+                        for gx in 0..3 {
+                            if bits & (0b100 >> gx) != 0 {
+                                fill(
+                                    text_left + (i * 4 + gx) * scale,
+                                    text_top + gy * scale,
+                                    scale,
+                                    scale,
+                                    color,
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        pixels
+    }
+}
+
+/// Parses a `#rrggbb` color
+fn rgb(hex: &str) -> [u8; 3] {
+    let channel = |i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+    [channel(1), channel(3), channel(5)]
+}
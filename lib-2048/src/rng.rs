@@ -0,0 +1,128 @@
+use std::fmt;
+
+use rand::{rngs::OsRng, Error, RngCore, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A snapshot of a seeded [`GameBoard`](crate::GameBoard)'s random number
+/// generator, from [`rng_state`](crate::GameBoard::rng_state)
+///
+/// Restoring it with
+/// [`restore_rng_state`](crate::GameBoard::restore_rng_state) makes future
+/// spawns continue exactly where the snapshot left off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RngState {
+    /// The seed the generator was created with
+    pub seed: u64,
+    /// The number of 32-bit words the generator has produced so far
+    pub word_pos: u128,
+}
+
+/// The source of randomness used to spawn tiles
+#[derive(Debug, Clone)]
+pub(crate) enum SpawnRng {
+    /// Unpredictable spawns straight from the operating system
+    Os(OsRng),
+    /// Reproducible spawns from a seed
+    ///
+    /// The generator is boxed so that unseeded boards, and the history
+    /// entries that copy the generator, don't pay for its size.
+    Seeded { seed: u64, rng: Box<ChaCha12Rng> },
+    /// Spawns from a generator handed to
+    /// [`new_with_rng`](crate::GameBoard::new_with_rng)
+    Custom(Box<dyn CloneRng>),
+}
+
+/// A random number generator that can be cloned along with the board it
+/// belongs to
+pub(crate) trait CloneRng: RngCore + Send + Sync {
+    fn clone_box(&self) -> Box<dyn CloneRng>;
+}
+
+impl<R: RngCore + Clone + Send + Sync + 'static> CloneRng for R {
+    fn clone_box(&self) -> Box<dyn CloneRng> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn CloneRng> {
+    fn clone(&self) -> Self {
+        // the box is a `CloneRng` itself, so this has to clone what's inside
+        // it instead of recursing
+        (**self).clone_box()
+    }
+}
+
+impl fmt::Debug for dyn CloneRng {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CustomRng")
+    }
+}
+
+impl SpawnRng {
+    pub(crate) fn new(seed: Option<u64>) -> Self {
+        match seed {
+            Some(seed) => Self::Seeded {
+                seed,
+                rng: Box::new(ChaCha12Rng::seed_from_u64(seed)),
+            },
+            None => Self::Os(OsRng),
+        }
+    }
+
+    pub(crate) fn from_state(state: RngState) -> Self {
+        let mut rng = ChaCha12Rng::seed_from_u64(state.seed);
+        rng.set_word_pos(state.word_pos);
+
+        Self::Seeded {
+            seed: state.seed,
+            rng: Box::new(rng),
+        }
+    }
+
+    pub(crate) fn state(&self) -> Option<RngState> {
+        match self {
+            Self::Os(_) | Self::Custom(_) => None,
+            Self::Seeded { seed, rng } => Some(RngState {
+                seed: *seed,
+                word_pos: rng.get_word_pos(),
+            }),
+        }
+    }
+}
+
+impl RngCore for SpawnRng {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            Self::Os(rng) => rng.next_u32(),
+            Self::Seeded { rng, .. } => rng.next_u32(),
+            Self::Custom(rng) => rng.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            Self::Os(rng) => rng.next_u64(),
+            Self::Seeded { rng, .. } => rng.next_u64(),
+            Self::Custom(rng) => rng.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            Self::Os(rng) => rng.fill_bytes(dest),
+            Self::Seeded { rng, .. } => rng.fill_bytes(dest),
+            Self::Custom(rng) => rng.fill_bytes(dest),
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        match self {
+            Self::Os(rng) => rng.try_fill_bytes(dest),
+            Self::Seeded { rng, .. } => rng.try_fill_bytes(dest),
+            Self::Custom(rng) => rng.try_fill_bytes(dest),
+        }
+    }
+}
@@ -0,0 +1,276 @@
+//! Saving and loading a [`GameBoard`] with serde
+//!
+//! A board is stored as its cells, with vacant spaces written as `0`, along
+//! with its score, move count, [version](GameBoard::version), biggest merge,
+//! whether it was [ever won](GameBoard::ever_won) or
+//! [ended early](GameBoard::end_game), the [age](GameBoard::tile_age) of
+//! each tile, how many [undos](GameBoard::undos_remaining) are left, rules,
+//! seeded RNG state, and a [`checksum`](GameBoard::checksum) that must match
+//! when it's loaded again. The moves that could be undone, the
+//! [score breakdown](GameBoard::score_breakdown), the
+//! [session best](GameBoard::session_best), the
+//! [spawn queue](GameBoard::set_spawn_queue), where the
+//! [direction cycle](GameBoard::next_scripted_direction) is up to, any open
+//! [combo](GameConfig::combo) window, and move timing aren't kept.
+//!
+//! As JSON, from [`GameBoard::to_json`], that looks like:
+//!
+//! ```json
+//! {
+//!   "cells": [[2, 0, 0, 0], [0, 0, 0, 0], [0, 0, 4, 0], [0, 0, 0, 0]],
+//!   "score": 0,
+//!   "moves": 0,
+//!   "version": 0,
+//!   "biggest_merge": 0,
+//!   "ever_won": false,
+//!   "ended": false,
+//!   "ages": [[0, 0, 0, 0], [0, 0, 0, 0], [0, 0, 0, 0], [0, 0, 0, 0]],
+//!   "undos_remaining": null,
+//!   "config": {
+//!     "starting_tiles": 2,
+//!     "spawn_distribution": [[2, 0.9], [4, 0.1]],
+//!     "win_target": 2048,
+//!     "win_behavior": "Continue",
+//!     "spawn_per_move": 1,
+//!     "seed": 7,
+//!     "wrap": false,
+//!     "bonus_threshold": null,
+//!     "spawn_policy": "Uniform",
+//!     "move_limit": null,
+//!     "allowed_directions": [true, true, true, true],
+//!     "direction_cycle": ["Left", "Up", "Right", "Down"],
+//!     "undos": null,
+//!     "min_merge_value": 0,
+//!     "per_move_score": 0,
+//!     "spawn_score": "Nothing",
+//!     "decay": null,
+//!     "combo": null,
+//!     "cascade_merges": false
+//!   },
+//!   "rng": { "seed": 7, "word_pos": 16 },
+//!   "checksum": 1234567890
+//! }
+//! ```
+//!
+//! - `cells` holds the rows from top to bottom, each from left to right, so
+//!   `cells[y][x]` is the tile at `(x, y)`. Vacant spaces are `0`.
+//! - `ages` is laid out like `cells`, and is all `0` unless tiles
+//!   [decay](GameConfig::decay).
+//! - `undos_remaining` is `null` when undos are unlimited, and a save
+//!   without it starts with the number of [undos](GameConfig::undos) the
+//!   rules allow.
+//! - `config` is a [`GameConfig`], and any rule left out takes its default.
+//! - `rng` is `null` for boards that aren't seeded.
+//! - `checksum` is [`GameBoard::checksum`] as a number.
+//!
+//! Existing fields keep their names and meanings; new fields are only ever
+//! added with defaults, so older saves keep loading.
+//!
+//! Frontends that only show a board can use [`GameBoard::to_ui_json`]
+//! instead, which can't be loaded again but includes what they'd otherwise
+//! work out from the cells:
+//!
+//! ```json
+//! {
+//!   "format": 1,
+//!   "cells": [[2, 0, 0, 0], [0, 0, 0, 0], [0, 0, 4, 0], [0, 0, 0, 0]],
+//!   "score": 0,
+//!   "moves": 0,
+//!   "is_won": false,
+//!   "is_lost": false,
+//!   "legal_moves": ["Left", "Up", "Right", "Down"],
+//!   "highest_tile": 4
+//! }
+//! ```
+//!
+//! - `format` is bumped whenever a field changes name or meaning.
+//! - `is_won` is [`GameBoard::has_winning_tile`], and `is_lost` is
+//!   [`GameBoard::has_lost`].
+//! - `highest_tile` is `null` for an empty board.
+
+use std::{error::Error, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    valid_distribution, BoardSpace, GameBoard, GameConfig, MoveDirection,
+    RngState, GAME_BOARD_SIZE,
+};
+
+/// The version of the format written by [`GameBoard::to_ui_json`]
+const UI_FORMAT: u32 = 1;
+
+/// The serialized form of a [`GameBoard`]
+#[derive(Serialize, Deserialize)]
+pub struct SavedGame {
+    cells: [[usize; GAME_BOARD_SIZE]; GAME_BOARD_SIZE],
+    score: usize,
+    moves: usize,
+    #[serde(default)]
+    version: u64,
+    #[serde(default)]
+    biggest_merge: usize,
+    #[serde(default)]
+    ever_won: bool,
+    #[serde(default)]
+    ended: bool,
+    #[serde(default)]
+    ages: [[usize; GAME_BOARD_SIZE]; GAME_BOARD_SIZE],
+    #[serde(default)]
+    undos_remaining: Option<usize>,
+    config: GameConfig,
+    rng: Option<RngState>,
+    checksum: u32,
+}
+
+/// What a frontend needs to show a [`GameBoard`], from
+/// [`GameBoard::to_ui_json`]
+#[derive(Serialize)]
+struct UiBoard {
+    format: u32,
+    cells: [[usize; GAME_BOARD_SIZE]; GAME_BOARD_SIZE],
+    score: usize,
+    moves: usize,
+    is_won: bool,
+    is_lost: bool,
+    legal_moves: Vec<MoveDirection>,
+    highest_tile: Option<usize>,
+}
+
+/// The error returned when a saved board can't be turned back into a
+/// [`GameBoard`]
+#[derive(Debug)]
+pub enum LoadError {
+    /// No tile in the spawn distribution can spawn, or a chance is negative
+    InvalidDistribution,
+    /// The checksum doesn't match the board's contents
+    ChecksumMismatch { expected: u32, actual: u32 },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidDistribution => {
+                write!(f, "spawn distribution has no valid chances")
+            },
+            Self::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "checksum mismatch: saved as {expected:08x}, but board is \
+                 {actual:08x}"
+            ),
+        }
+    }
+}
+
+impl From<GameBoard> for SavedGame {
+    fn from(board: GameBoard) -> Self {
+        Self {
+            cells: board
+                .cells
+                .map(|row| row.map(|space| space.value().unwrap_or(0))),
+            score: board.score,
+            moves: board.moves,
+            version: board.version,
+            biggest_merge: board.biggest_merge,
+            ever_won: board.ever_won,
+            ended: board.ended,
+            ages: board.ages,
+            undos_remaining: board.undos_remaining,
+            checksum: board.checksum(),
+            rng: board.rng_state(),
+            config: board.config,
+        }
+    }
+}
+
+impl TryFrom<SavedGame> for GameBoard {
+    type Error = LoadError;
+
+    fn try_from(saved: SavedGame) -> Result<Self, Self::Error> {
+        if !valid_distribution(&saved.config.spawn_distribution) {
+            return Err(LoadError::InvalidDistribution);
+        }
+
+        let cells = saved.cells.map(|row| {
+            row.map(|value| match value {
+                0 => BoardSpace::Vacant,
+                t => BoardSpace::Tile(t),
+            })
+        });
+
+        let mut board =
+            Self::with_cells_and_config(saved.config, Some(cells), saved.score);
+        board.moves = saved.moves;
+        board.version = saved.version;
+        board.biggest_merge = saved.biggest_merge;
+        board.ever_won |= saved.ever_won;
+        board.ended = saved.ended;
+        board.ages = saved.ages;
+        if saved.undos_remaining.is_some() {
+            board.undos_remaining = saved.undos_remaining;
+        }
+
+        if let Some(state) = saved.rng {
+            board.restore_rng_state(state);
+        }
+
+        let actual = board.checksum();
+        if actual != saved.checksum {
+            return Err(LoadError::ChecksumMismatch {
+                expected: saved.checksum,
+                actual,
+            });
+        }
+
+        Ok(board)
+    }
+}
+
+/// The error returned when a board can't be loaded from JSON, because the
+/// JSON is malformed, its spawn distribution is invalid, or its checksum
+/// doesn't match
+#[derive(Debug)]
+pub struct JsonError(serde_json::Error);
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Error for JsonError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl GameBoard {
+    /// Saves the board as JSON, in the [format](self) described above
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("boards always serialize")
+    }
+
+    /// Loads a board saved with [`to_json`](Self::to_json)
+    pub fn from_json(s: &str) -> Result<Self, JsonError> {
+        serde_json::from_str(s).map_err(JsonError)
+    }
+
+    /// Writes the board as JSON for a frontend to show, in the
+    /// [format](self) described above
+    pub fn to_ui_json(&self) -> String {
+        let ui = UiBoard {
+            format: UI_FORMAT,
+            cells: self
+                .cells
+                .map(|row| row.map(|space| space.value().unwrap_or(0))),
+            score: self.score,
+            moves: self.moves,
+            is_won: self.has_winning_tile(),
+            is_lost: self.has_lost(),
+            legal_moves: self.legal_moves(),
+            highest_tile: self.highest_tile(),
+        };
+
+        serde_json::to_string(&ui).expect("boards always serialize")
+    }
+}
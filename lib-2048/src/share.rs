@@ -0,0 +1,993 @@
+//! Short codes that a [`GameBoard`] can be shared and rebuilt from
+//!
+//! A share code is the board's cells, score, and rules packed into bytes and
+//! written as unpadded base64url, so it can be pasted into chat or a URL.
+//! The first byte is the format version, and codes made with a version this
+//! library doesn't know are turned away instead of being read wrong. Codes
+//! from older versions are still read, with any rules they're missing left
+//! at their defaults.
+//!
+//! Numbers are written as LEB128 varints, so small ones take up one byte. A
+//! board played with the [default](GameConfig::default) rules stores a
+//! single byte in place of its rules, keeping most codes around 30
+//! characters.
+//!
+//! [Debug codes](GameBoard::debug_code) are meant for bug reports instead.
+//! They're longer, but can be read by eye, and keep everything needed to
+//! make the board's next move again exactly.
+//!
+//! For storing lots of boards, [`GameBoard::to_bytes`] keeps the same things
+//! as a [JSON save](GameBoard::to_json) as raw bytes, with no base64. Like
+//! share codes, its first byte is the format version.
+
+use std::{error::Error, fmt};
+
+use std::time::Duration;
+
+use crate::{
+    valid_distribution, BoardSpace, Combo, ComboWindow, Decay, DecayFloor,
+    GameBoard, GameConfig, LastMerge, MoveDirection, RngState, SpawnPolicy,
+    SpawnScore, WinBehavior, GAME_BOARD_SIZE,
+};
+
+/// The version of the format written by [`GameBoard::to_share_code`]
+///
+/// - 1: The first version
+/// - 2: Adds [`min_merge_value`](GameConfig::min_merge_value)
+/// - 3: Adds [`per_move_score`](GameConfig::per_move_score) and
+///   [`spawn_score`](GameConfig::spawn_score)
+/// - 4: Adds [`decay`](GameConfig::decay)
+/// - 5: Adds [`combo`](GameConfig::combo), with time windows kept to the
+///   millisecond
+/// - 6: Adds [`cascade_merges`](GameConfig::cascade_merges)
+const VERSION: u8 = 6;
+
+/// The version of the format written by [`GameBoard::debug_code`]
+///
+/// - 1: The first version, with the rules written like in a version
+///   [`DEBUG_V1_CONFIG`] share code
+/// - 2: The rules start with the share code version they were written with
+const DEBUG_VERSION: u8 = 2;
+
+/// The share code version of the rules in a version 1 debug code
+const DEBUG_V1_CONFIG: u8 = 3;
+
+/// The version of the format written by [`GameBoard::to_bytes`]
+const BYTES_VERSION: u8 = 1;
+
+/// Flags in the second byte written by [`GameBoard::to_bytes`], for the
+/// parts of a board that are only written when they're needed
+const BYTES_CONFIG: u8 = 1 << 0;
+const BYTES_WIDE_CELLS: u8 = 1 << 1;
+const BYTES_AGES: u8 = 1 << 2;
+const BYTES_RNG: u8 = 1 << 3;
+const BYTES_EVER_WON: u8 = 1 << 4;
+const BYTES_ENDED: u8 = 1 << 5;
+const BYTES_UNDOS: u8 = 1 << 6;
+
+const ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// The error returned when a board can't be rebuilt from a share code, a
+/// debug code, or [bytes](GameBoard::from_bytes)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareCodeError {
+    /// A share code has a character that isn't used in base64url
+    InvalidCharacter(char),
+    /// The data was written with a format version this library can't read
+    UnsupportedVersion(u8),
+    /// The data is cut short, has extra data, or holds impossible rules
+    Malformed,
+}
+
+impl fmt::Display for ShareCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCharacter(c) => {
+                write!(f, "`{c}` can't be part of a share code")
+            },
+            Self::UnsupportedVersion(v) => {
+                write!(f, "format version {v} isn't supported")
+            },
+            Self::Malformed => write!(f, "saved board is damaged"),
+        }
+    }
+}
+
+impl Error for ShareCodeError {}
+
+impl GameBoard {
+    /// Packs the board's cells, score, and rules into a short code that
+    /// [`from_share_code`](Self::from_share_code) can rebuild it from
+    ///
+    /// The move count, history, and the position of a seeded board's random
+    /// number generator aren't included, so a rebuilt seeded board spawns
+    /// tiles as if it had just been created.
+    ///
+    /// ```
+    /// use lib_2048::GameBoard;
+    ///
+    /// let board = GameBoard::new();
+    /// let code = board.to_share_code();
+    ///
+    /// let shared = GameBoard::from_share_code(&code).unwrap();
+    /// assert_eq!(shared.cells, board.cells);
+    /// assert_eq!(shared.score, board.score);
+    /// ```
+    pub fn to_share_code(&self) -> String {
+        let mut w = Writer::default();
+        w.byte(VERSION);
+
+        for (_, space) in self.spaces() {
+            w.uint(space.value().unwrap_or(0));
+        }
+        w.uint(self.score);
+
+        let config = self.config();
+        if *config == GameConfig::default() {
+            w.byte(0);
+        } else {
+            w.byte(1);
+            write_config(&mut w, config);
+        }
+
+        encode(&w.0)
+    }
+
+    /// Rebuilds a board from a code made by
+    /// [`to_share_code`](Self::to_share_code)
+    ///
+    /// ```
+    /// use lib_2048::{GameBoard, ShareCodeError};
+    ///
+    /// assert_eq!(
+    ///     GameBoard::from_share_code("oops!").unwrap_err(),
+    ///     ShareCodeError::InvalidCharacter('!')
+    /// );
+    /// ```
+    pub fn from_share_code(code: &str) -> Result<Self, ShareCodeError> {
+        let bytes = decode(code)?;
+        let mut r = Reader(&bytes);
+
+        let version = r.byte()?;
+        if !(1..=VERSION).contains(&version) {
+            return Err(ShareCodeError::UnsupportedVersion(version));
+        }
+
+        let mut cells =
+            [[BoardSpace::Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE];
+        for space in cells.iter_mut().flatten() {
+            *space = match r.uint()? {
+                0 => BoardSpace::Vacant,
+                t => BoardSpace::Tile(t),
+            };
+        }
+        let score = r.uint()?;
+
+        let config = match r.byte()? {
+            0 => GameConfig::default(),
+            1 => read_config(&mut r, version)?,
+            _ => return Err(ShareCodeError::Malformed),
+        };
+
+        if !r.0.is_empty() {
+            return Err(ShareCodeError::Malformed);
+        }
+
+        Ok(Self::with_cells_and_config(config, Some(cells), score))
+    }
+
+    /// Writes the board on one line for pasting into a bug report, so
+    /// [`from_debug_code`](Self::from_debug_code) can rebuild it exactly
+    ///
+    /// The code starts with the format version and the cells, with rows
+    /// split by `/`. Each tile is written as its power of two, or as `#` and
+    /// its value if it isn't one, and vacant spaces are `.`. After that come
+    /// the score and move count, then only the parts of the board that
+    /// differ from a new one: the position in the
+    /// [`direction_cycle`](GameConfig::direction_cycle), the undos left,
+    /// whether it was [ever won](Self::ever_won) or
+    /// [ended](Self::end_game), the move count after the last move that
+    /// merged tiles while [combos](GameConfig::combo) are on, the
+    /// [age](Self::tile_age) of each tile laid out like the cells, the
+    /// state of a seeded board's random number generator, and the rules as
+    /// they're written in a share code, after the share code version they
+    /// were written with so they can still be read once that format
+    /// changes.
+    ///
+    /// ```
+    /// use lib_2048::{BoardSpace::*, GameBoard};
+    ///
+    /// let board = GameBoard::from([
+    ///     [Tile(2), Tile(4), Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Vacant],
+    ///     [Vacant, Vacant, Vacant, Tile(3)],
+    ///     [Vacant, Vacant, Vacant, Tile(1024)],
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     board.debug_code(),
+    ///     "d2 1,2,.,./.,.,.,./.,.,.,#3/.,.,.,10 score=0 moves=0"
+    /// );
+    /// ```
+    pub fn debug_code(&self) -> String {
+        let rows = self
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .map(|space| match space.value() {
+                        None => ".".to_string(),
+                        Some(t) if t.is_power_of_two() => {
+                            t.trailing_zeros().to_string()
+                        },
+                        Some(t) => format!("#{t}"),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let mut code = format!(
+            "d{DEBUG_VERSION} {rows} score={} moves={}",
+            self.score, self.moves
+        );
+
+        if self.cycle_pos != 0 {
+            code += &format!(" cycle={}", self.cycle_pos);
+        }
+        if let Some(undos) = self.undos_remaining {
+            code += &format!(" undos={undos}");
+        }
+        if self.ever_won {
+            code += " won";
+        }
+        if self.ended {
+            code += " ended";
+        }
+        if let Some(last) = self.last_merge {
+            code += &format!(" merged={}", last.moves);
+        }
+        if self.ages.iter().flatten().any(|age| *age != 0) {
+            let ages = self
+                .ages
+                .map(|row| row.map(|age| age.to_string()).join(","))
+                .join("/");
+            code += &format!(" ages={ages}");
+        }
+        if let Some(state) = self.rng_state() {
+            code += &format!(" rng={}:{}", state.seed, state.word_pos);
+        }
+        if *self.config() != GameConfig::default() {
+            let mut w = Writer::default();
+            write_config(&mut w, self.config());
+            code += &format!(" config={VERSION}:{}", encode(&w.0));
+        }
+
+        code
+    }
+
+    /// Rebuilds a board from a code made by
+    /// [`debug_code`](Self::debug_code)
+    ///
+    /// ```
+    /// use lib_2048::{GameBoard, MoveDirection};
+    ///
+    /// let mut board = GameBoard::with_seed(9);
+    /// board.r#move(MoveDirection::Left);
+    ///
+    /// let code = board.debug_code();
+    /// let mut rebuilt = GameBoard::from_debug_code(&code).unwrap();
+    /// assert_eq!(rebuilt.moves(), 1);
+    ///
+    /// board.r#move(MoveDirection::Up);
+    /// rebuilt.r#move(MoveDirection::Up);
+    /// assert_eq!(rebuilt.cells, board.cells);
+    /// ```
+    pub fn from_debug_code(code: &str) -> Result<Self, ShareCodeError> {
+        let mut parts = code.split_whitespace();
+
+        let version = parts.next().ok_or(ShareCodeError::Malformed)?;
+        let version = match version.strip_prefix('d').map(str::parse::<u8>) {
+            Some(Ok(v)) if (1..=DEBUG_VERSION).contains(&v) => v,
+            Some(Ok(v)) => return Err(ShareCodeError::UnsupportedVersion(v)),
+            _ => return Err(ShareCodeError::Malformed),
+        };
+
+        let rows = parts.next().ok_or(ShareCodeError::Malformed)?;
+        let mut cells =
+            [[BoardSpace::Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE];
+        let mut spaces = rows.split('/').flat_map(|row| row.split(','));
+        for space in cells.iter_mut().flatten() {
+            *space = parse_debug_space(spaces.next())?;
+        }
+        if spaces.next().is_some() {
+            return Err(ShareCodeError::Malformed);
+        }
+
+        let parts = parts
+            .map(|part| part.split_once('=').unwrap_or((part, "")))
+            .collect::<Vec<_>>();
+
+        // the rules are needed to build the board that everything else is
+        // set on
+        let config = match parts.iter().find(|(key, _)| *key == "config") {
+            Some((_, value)) => {
+                let (config_version, value) = match version {
+                    1 => (DEBUG_V1_CONFIG, *value),
+                    _ => {
+                        let (config_version, value) = value
+                            .split_once(':')
+                            .ok_or(ShareCodeError::Malformed)?;
+                        (parse_number(config_version)?, value)
+                    },
+                };
+                if !(1..=VERSION).contains(&config_version) {
+                    return Err(ShareCodeError::UnsupportedVersion(
+                        config_version,
+                    ));
+                }
+
+                let bytes = decode(value)?;
+                let mut r = Reader(&bytes);
+                let config = read_config(&mut r, config_version)?;
+                if !r.0.is_empty() {
+                    return Err(ShareCodeError::Malformed);
+                }
+
+                config
+            },
+            None => GameConfig::default(),
+        };
+
+        let mut board = Self::with_cells_and_config(config, Some(cells), 0);
+        let mut rng = None;
+
+        for (key, value) in parts {
+            match key {
+                "score" => board.score = parse_number(value)?,
+                "moves" => board.moves = parse_number(value)?,
+                "cycle" => board.cycle_pos = parse_number(value)?,
+                "undos" => board.undos_remaining = Some(parse_number(value)?),
+                "won" => board.ever_won = true,
+                "ended" => board.ended = true,
+                "merged" => {
+                    board.last_merge = Some(LastMerge {
+                        moves: parse_number(value)?,
+                        at: None,
+                    });
+                },
+                "ages" => {
+                    let mut ages = value.split('/').flat_map(|r| r.split(','));
+                    for age in board.ages.iter_mut().flatten() {
+                        *age = parse_number(
+                            ages.next().ok_or(ShareCodeError::Malformed)?,
+                        )?;
+                    }
+                    if ages.next().is_some() {
+                        return Err(ShareCodeError::Malformed);
+                    }
+                },
+                "rng" => {
+                    let (seed, word_pos) = value
+                        .split_once(':')
+                        .ok_or(ShareCodeError::Malformed)?;
+                    rng = Some(RngState {
+                        seed: parse_number(seed)?,
+                        word_pos: parse_number(word_pos)?,
+                    });
+                },
+                "config" => {},
+                _ => return Err(ShareCodeError::Malformed),
+            }
+        }
+
+        if let Some(state) = rng {
+            board.restore_rng_state(state);
+        }
+
+        Ok(board)
+    }
+}
+
+impl GameBoard {
+    /// Packs the board into bytes that [`from_bytes`](Self::from_bytes) can
+    /// rebuild it from, for storing many boards compactly
+    ///
+    /// This keeps the same parts of a board as a [JSON save](Self::to_json),
+    /// apart from its checksum: the cells, score, move count,
+    /// [version](Self::version), [biggest merge](Self::biggest_merge),
+    /// whether it was [ever won](Self::ever_won) or
+    /// [ended early](Self::end_game), [tile ages](Self::tile_age),
+    /// [undos left](Self::undos_remaining), rules, and a seeded board's
+    /// random number generator. It leaves out the same parts, like the moves
+    /// that could be undone and the
+    /// [score breakdown](Self::score_breakdown).
+    ///
+    /// After the version comes a byte of flags, for the optional parts and
+    /// whether the board was ever won or ended early, then the cells. When
+    /// every tile is a power of two from 2 to 32768, each space takes four
+    /// bits, holding the tile's power of two or 0 if it's vacant.
+    /// Otherwise, each space is a varint holding its value. The score, move
+    /// count, [version](Self::version), and
+    /// [biggest merge](Self::biggest_merge) follow as varints, along with
+    /// the [undos left](Self::undos_remaining) if some have been used. Then
+    /// come the [tile ages](Self::tile_age) if any aren't 0, the rules if
+    /// they aren't the defaults, and the state of a seeded board's random
+    /// number generator. The rules are written like in a share code, after
+    /// the share code version they were written with, so they can still be
+    /// read once that format changes.
+    ///
+    /// A new board with the default rules and no seed takes 14 bytes.
+    ///
+    /// ```
+    /// use lib_2048::GameBoard;
+    ///
+    /// let board = GameBoard::with_seed(4);
+    /// let bytes = board.to_bytes();
+    ///
+    /// let rebuilt = GameBoard::from_bytes(&bytes).unwrap();
+    /// assert_eq!(rebuilt.cells, board.cells);
+    /// assert_eq!(rebuilt.config(), board.config());
+    /// assert_eq!(rebuilt.rng_state(), board.rng_state());
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let narrow = self.spaces().all(|(_, space)| match space.value() {
+            None => true,
+            Some(t) => t.is_power_of_two() && (1..16).contains(&t.ilog2()),
+        });
+        let ages = self.ages.iter().flatten().any(|age| *age != 0);
+        let config = *self.config() != GameConfig::default();
+        let rng = self.rng_state();
+        let undos = self
+            .undos_remaining
+            .filter(|_| self.undos_remaining != self.config.undos);
+
+        let flags = [
+            (config, BYTES_CONFIG),
+            (!narrow, BYTES_WIDE_CELLS),
+            (ages, BYTES_AGES),
+            (rng.is_some(), BYTES_RNG),
+            (self.ever_won, BYTES_EVER_WON),
+            (self.ended, BYTES_ENDED),
+            (undos.is_some(), BYTES_UNDOS),
+        ]
+        .iter()
+        .filter(|(set, _)| *set)
+        .fold(0, |flags, (_, flag)| flags | flag);
+
+        let mut w = Writer::default();
+        w.byte(BYTES_VERSION);
+        w.byte(flags);
+
+        if narrow {
+            let nibbles = self
+                .spaces()
+                .map(|(_, space)| space.value().map_or(0, |t| t.ilog2() as u8))
+                .collect::<Vec<_>>();
+            for pair in nibbles.chunks_exact(2) {
+                w.byte(pair[0] << 4 | pair[1]);
+            }
+        } else {
+            for (_, space) in self.spaces() {
+                w.uint(space.value().unwrap_or(0));
+            }
+        }
+
+        w.uint(self.score);
+        w.uint(self.moves);
+        w.u64(self.version);
+        w.uint(self.biggest_merge);
+        if let Some(undos) = undos {
+            w.uint(undos);
+        }
+
+        if ages {
+            for age in self.ages.iter().flatten() {
+                w.uint(*age);
+            }
+        }
+        if config {
+            w.byte(VERSION);
+            write_config(&mut w, self.config());
+        }
+        if let Some(state) = rng {
+            w.u64(state.seed);
+            w.0.extend(state.word_pos.to_le_bytes());
+        }
+
+        w.0
+    }
+
+    /// Rebuilds a board from bytes made by [`to_bytes`](Self::to_bytes)
+    ///
+    /// ```
+    /// use lib_2048::{GameBoard, ShareCodeError};
+    ///
+    /// assert_eq!(
+    ///     GameBoard::from_bytes(&[9]).unwrap_err(),
+    ///     ShareCodeError::UnsupportedVersion(9)
+    /// );
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ShareCodeError> {
+        let mut r = Reader(bytes);
+
+        let version = r.byte()?;
+        if !(1..=BYTES_VERSION).contains(&version) {
+            return Err(ShareCodeError::UnsupportedVersion(version));
+        }
+
+        let flags = r.byte()?;
+        let has = |flag| flags & flag != 0;
+
+        let mut cells =
+            [[BoardSpace::Vacant; GAME_BOARD_SIZE]; GAME_BOARD_SIZE];
+        if has(BYTES_WIDE_CELLS) {
+            for space in cells.iter_mut().flatten() {
+                *space = match r.uint()? {
+                    0 => BoardSpace::Vacant,
+                    t => BoardSpace::Tile(t),
+                };
+            }
+        } else {
+            let mut nibbles = Vec::with_capacity(GAME_BOARD_SIZE.pow(2));
+            while nibbles.len() < GAME_BOARD_SIZE.pow(2) {
+                let byte = r.byte()?;
+                nibbles.extend([byte >> 4, byte & 0xf]);
+            }
+
+            for (space, nibble) in cells.iter_mut().flatten().zip(nibbles) {
+                *space = match nibble {
+                    0 => BoardSpace::Vacant,
+                    n => BoardSpace::Tile(1 << n),
+                };
+            }
+        }
+
+        let score = r.uint()?;
+        let moves = r.uint()?;
+        let board_version = r.u64()?;
+        let biggest_merge = r.uint()?;
+        let undos = if has(BYTES_UNDOS) {
+            Some(r.uint()?)
+        } else {
+            None
+        };
+
+        let mut ages = [[0; GAME_BOARD_SIZE]; GAME_BOARD_SIZE];
+        if has(BYTES_AGES) {
+            for age in ages.iter_mut().flatten() {
+                *age = r.uint()?;
+            }
+        }
+
+        let config = if has(BYTES_CONFIG) {
+            let config_version = r.byte()?;
+            if !(1..=VERSION).contains(&config_version) {
+                return Err(ShareCodeError::UnsupportedVersion(config_version));
+            }
+
+            read_config(&mut r, config_version)?
+        } else {
+            GameConfig::default()
+        };
+
+        let rng = if has(BYTES_RNG) {
+            Some(RngState {
+                seed: r.u64()?,
+                word_pos: u128::from_le_bytes(r.take()?),
+            })
+        } else {
+            None
+        };
+
+        if !r.0.is_empty() {
+            return Err(ShareCodeError::Malformed);
+        }
+
+        let mut board = Self::with_cells_and_config(config, Some(cells), score);
+        board.moves = moves;
+        board.version = board_version;
+        board.biggest_merge = biggest_merge;
+        board.ages = ages;
+        if undos.is_some() {
+            board.undos_remaining = undos;
+        }
+        board.ever_won |= has(BYTES_EVER_WON);
+        board.ended = has(BYTES_ENDED);
+
+        if let Some(state) = rng {
+            board.restore_rng_state(state);
+        }
+
+        Ok(board)
+    }
+}
+
+/// Reads a space written by [`GameBoard::debug_code`]
+fn parse_debug_space(
+    space: Option<&str>,
+) -> Result<BoardSpace, ShareCodeError> {
+    let space = space.ok_or(ShareCodeError::Malformed)?;
+
+    if space == "." {
+        return Ok(BoardSpace::Vacant);
+    }
+
+    let value = match space.strip_prefix('#') {
+        Some(value) => parse_number(value)?,
+        None => 1usize
+            .checked_shl(parse_number(space)?)
+            .ok_or(ShareCodeError::Malformed)?,
+    };
+
+    match value {
+        0 => Err(ShareCodeError::Malformed),
+        t => Ok(BoardSpace::Tile(t)),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(s: &str) -> Result<T, ShareCodeError> {
+    s.parse().map_err(|_| ShareCodeError::Malformed)
+}
+
+fn write_config(w: &mut Writer, config: &GameConfig) {
+    w.uint(config.starting_tiles);
+
+    w.uint(config.spawn_distribution.len());
+    for (value, chance) in &config.spawn_distribution {
+        w.uint(*value);
+        w.0.extend(chance.to_le_bytes());
+    }
+
+    w.uint(config.win_target);
+    w.byte(match config.win_behavior {
+        WinBehavior::Stop => 0,
+        WinBehavior::Prompt => 1,
+        WinBehavior::Continue => 2,
+    });
+    w.uint(config.spawn_per_move);
+    w.option_u64(config.seed);
+    w.byte(config.wrap as u8);
+    w.option(config.bonus_threshold);
+    w.byte(match config.spawn_policy {
+        SpawnPolicy::Uniform => 0,
+        SpawnPolicy::AvoidHighestTile => 1,
+        SpawnPolicy::KeepAlive => 2,
+    });
+    w.option(config.move_limit);
+
+    let allowed = config
+        .allowed_directions
+        .iter()
+        .enumerate()
+        .fold(0, |bits, (i, allowed)| bits | (*allowed as u8) << i);
+    w.byte(allowed);
+
+    w.uint(config.direction_cycle.len());
+    for dir in &config.direction_cycle {
+        w.byte(*dir as u8);
+    }
+
+    w.option(config.undos);
+    w.uint(config.min_merge_value);
+
+    w.uint(config.per_move_score);
+    match config.spawn_score {
+        SpawnScore::Nothing => w.byte(0),
+        SpawnScore::Flat(points) => {
+            w.byte(1);
+            w.uint(points);
+        },
+        SpawnScore::TileValue => w.byte(2),
+    }
+
+    match config.decay {
+        None => w.byte(0),
+        Some(decay) => {
+            w.byte(1);
+            w.uint(decay.after_moves);
+            w.byte(match decay.floor {
+                DecayFloor::Vanish => 0,
+                DecayFloor::Keep => 1,
+            });
+        },
+    }
+
+    match config.combo {
+        None => w.byte(0),
+        Some(combo) => {
+            match combo.window {
+                ComboWindow::Moves(moves) => {
+                    w.byte(1);
+                    w.uint(moves);
+                },
+                ComboWindow::Time(time) => {
+                    w.byte(2);
+                    w.u64(time.as_millis() as u64);
+                },
+            }
+            w.uint(combo.multiplier);
+        },
+    }
+
+    w.byte(config.cascade_merges as u8);
+}
+
+fn read_config(
+    r: &mut Reader,
+    version: u8,
+) -> Result<GameConfig, ShareCodeError> {
+    let starting_tiles = r.uint()?;
+
+    let mut spawn_distribution = Vec::new();
+    for _ in 0..r.uint()? {
+        let value = r.uint()?;
+        let chance = f64::from_le_bytes(r.take()?);
+        spawn_distribution.push((value, chance));
+    }
+
+    if !valid_distribution(&spawn_distribution) {
+        return Err(ShareCodeError::Malformed);
+    }
+
+    let win_target = r.uint()?;
+    let win_behavior = match r.byte()? {
+        0 => WinBehavior::Stop,
+        1 => WinBehavior::Prompt,
+        2 => WinBehavior::Continue,
+        _ => return Err(ShareCodeError::Malformed),
+    };
+    let spawn_per_move = r.uint()?;
+    let seed = r.option_u64()?;
+    let wrap = match r.byte()? {
+        0 => false,
+        1 => true,
+        _ => return Err(ShareCodeError::Malformed),
+    };
+    let bonus_threshold = r.option()?;
+    let spawn_policy = match r.byte()? {
+        0 => SpawnPolicy::Uniform,
+        1 => SpawnPolicy::AvoidHighestTile,
+        2 => SpawnPolicy::KeepAlive,
+        _ => return Err(ShareCodeError::Malformed),
+    };
+    let move_limit = r.option()?;
+
+    let allowed = r.byte()?;
+    let allowed_directions = [0, 1, 2, 3].map(|i: u8| allowed & (1 << i) != 0);
+
+    let mut direction_cycle = Vec::new();
+    for _ in 0..r.uint()? {
+        let dir = MoveDirection::all()
+            .get(r.byte()? as usize)
+            .copied()
+            .ok_or(ShareCodeError::Malformed)?;
+        direction_cycle.push(dir);
+    }
+
+    let undos = r.option()?;
+    let min_merge_value = if version >= 2 { r.uint()? } else { 0 };
+
+    let (per_move_score, spawn_score) = if version >= 3 {
+        let per_move_score = r.uint()?;
+        let spawn_score = match r.byte()? {
+            0 => SpawnScore::Nothing,
+            1 => SpawnScore::Flat(r.uint()?),
+            2 => SpawnScore::TileValue,
+            _ => return Err(ShareCodeError::Malformed),
+        };
+
+        (per_move_score, spawn_score)
+    } else {
+        (0, SpawnScore::Nothing)
+    };
+
+    let decay = if version >= 4 {
+        match r.byte()? {
+            0 => None,
+            1 => Some(Decay {
+                after_moves: r.uint()?,
+                floor: match r.byte()? {
+                    0 => DecayFloor::Vanish,
+                    1 => DecayFloor::Keep,
+                    _ => return Err(ShareCodeError::Malformed),
+                },
+            }),
+            _ => return Err(ShareCodeError::Malformed),
+        }
+    } else {
+        None
+    };
+
+    let combo = if version >= 5 {
+        let window = match r.byte()? {
+            0 => None,
+            1 => Some(ComboWindow::Moves(r.uint()?)),
+            2 => Some(ComboWindow::Time(Duration::from_millis(r.u64()?))),
+            _ => return Err(ShareCodeError::Malformed),
+        };
+
+        match window {
+            Some(window) => Some(Combo {
+                window,
+                multiplier: r.uint()?,
+            }),
+            None => None,
+        }
+    } else {
+        None
+    };
+
+    let cascade_merges = if version >= 6 {
+        match r.byte()? {
+            0 => false,
+            1 => true,
+            _ => return Err(ShareCodeError::Malformed),
+        }
+    } else {
+        false
+    };
+
+    Ok(GameConfig {
+        starting_tiles,
+        spawn_distribution,
+        win_target,
+        win_behavior,
+        spawn_per_move,
+        seed,
+        wrap,
+        bonus_threshold,
+        spawn_policy,
+        move_limit,
+        allowed_directions,
+        direction_cycle,
+        undos,
+        min_merge_value,
+        per_move_score,
+        spawn_score,
+        decay,
+        combo,
+        cascade_merges,
+    })
+}
+
+#[derive(Default)]
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn byte(&mut self, byte: u8) {
+        self.0.push(byte);
+    }
+
+    fn uint(&mut self, n: usize) {
+        self.u64(n as u64);
+    }
+
+    fn u64(&mut self, mut n: u64) {
+        while n >= 0x80 {
+            self.0.push(n as u8 | 0x80);
+            n >>= 7;
+        }
+
+        self.0.push(n as u8);
+    }
+
+    fn option(&mut self, n: Option<usize>) {
+        self.option_u64(n.map(|n| n as u64));
+    }
+
+    fn option_u64(&mut self, n: Option<u64>) {
+        match n {
+            Some(n) => {
+                self.byte(1);
+                self.u64(n);
+            },
+            None => self.byte(0),
+        }
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], ShareCodeError> {
+        if self.0.len() < N {
+            return Err(ShareCodeError::Malformed);
+        }
+
+        let (bytes, rest) = self.0.split_at(N);
+        self.0 = rest;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn byte(&mut self) -> Result<u8, ShareCodeError> {
+        self.take::<1>().map(|[byte]| byte)
+    }
+
+    fn uint(&mut self) -> Result<usize, ShareCodeError> {
+        usize::try_from(self.u64()?).map_err(|_| ShareCodeError::Malformed)
+    }
+
+    fn u64(&mut self) -> Result<u64, ShareCodeError> {
+        let mut n = 0u64;
+
+        for shift in (0..u64::BITS).step_by(7) {
+            let byte = self.byte()?;
+            n |= ((byte & 0x7f) as u64)
+                .checked_shl(shift)
+                .ok_or(ShareCodeError::Malformed)?;
+
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+        }
+
+        Err(ShareCodeError::Malformed)
+    }
+
+    fn option(&mut self) -> Result<Option<usize>, ShareCodeError> {
+        self.option_u64()?
+            .map(|n| usize::try_from(n).map_err(|_| ShareCodeError::Malformed))
+            .transpose()
+    }
+
+    fn option_u64(&mut self) -> Result<Option<u64>, ShareCodeError> {
+        match self.byte()? {
+            0 => Ok(None),
+            1 => self.u64().map(Some),
+            _ => Err(ShareCodeError::Malformed),
+        }
+    }
+}
+
+/// Writes `bytes` as unpadded base64url
+fn encode(bytes: &[u8]) -> String {
+    let mut code = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - i * 8));
+
+        for i in 0..=chunk.len() {
+            code.push(ALPHABET[(n >> (18 - i * 6)) as usize & 0x3f] as char);
+        }
+    }
+
+    code
+}
+
+/// Reads unpadded base64url
+fn decode(code: &str) -> Result<Vec<u8>, ShareCodeError> {
+    let sextets = code
+        .chars()
+        .map(|c| {
+            ALPHABET
+                .iter()
+                .position(|a| *a as char == c)
+                .map(|i| i as u32)
+                .ok_or(ShareCodeError::InvalidCharacter(c))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // a lone character left over can't hold a whole byte
+    if sextets.len() % 4 == 1 {
+        return Err(ShareCodeError::Malformed);
+    }
+
+    let mut bytes = Vec::with_capacity(sextets.len() * 3 / 4);
+    for chunk in sextets.chunks(4) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0, |n, (i, s)| n | s << (18 - i * 6));
+
+        for i in 0..chunk.len() - 1 {
+            bytes.push((n >> (16 - i * 8)) as u8);
+        }
+    }
+
+    Ok(bytes)
+}
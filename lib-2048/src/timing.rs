@@ -0,0 +1,115 @@
+//! Optional wall-clock tracking of the time spent between moves
+
+use std::{fmt, sync::Arc, time::Duration};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+/// A source of the current time, used to time moves on a
+/// [`GameBoard`](crate::GameBoard)
+///
+/// Implement this to drive timing from something other than the system
+/// clock, such as a fake clock in tests
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// Gets the time elapsed since some fixed point in the past
+    fn now(&self) -> Duration;
+}
+
+/// A [`Clock`] backed by [`Instant`]
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock(Instant);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SystemClock {
+    pub fn new() -> Self {
+        Self(Instant::now())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.0.elapsed()
+    }
+}
+
+/// Records the time taken by each move made on a board
+#[derive(Debug, Clone)]
+pub(crate) struct MoveTimer {
+    clock: Arc<dyn Clock>,
+    started_at: Duration,
+    last_move_at: Duration,
+    fastest_move: Option<Duration>,
+    timed_moves: usize,
+}
+
+impl MoveTimer {
+    pub(crate) fn new(clock: Arc<dyn Clock>) -> Self {
+        let now = clock.now();
+
+        Self {
+            clock,
+            started_at: now,
+            last_move_at: now,
+            fastest_move: None,
+            timed_moves: 0,
+        }
+    }
+
+    /// Records that a move was just made
+    pub(crate) fn record_move(&mut self) {
+        let now = self.clock.now();
+        let took = now.saturating_sub(self.last_move_at);
+
+        self.fastest_move = Some(match self.fastest_move {
+            Some(fastest) => fastest.min(took),
+            None => took,
+        });
+        self.last_move_at = now;
+        self.timed_moves += 1;
+    }
+
+    /// Starts a new timer on the same clock, for a new game
+    pub(crate) fn restarted(&self) -> Self {
+        Self::new(self.clock.clone())
+    }
+
+    /// Gets the time on the timer's clock
+    pub(crate) fn now(&self) -> Duration {
+        self.clock.now()
+    }
+
+    /// The time between the timer starting and the most recent move
+    pub(crate) fn total_play_time(&self) -> Duration {
+        self.last_move_at.saturating_sub(self.started_at)
+    }
+
+    pub(crate) fn fastest_move(&self) -> Option<Duration> {
+        self.fastest_move
+    }
+
+    pub(crate) fn average_move_time(&self) -> Option<Duration> {
+        if self.timed_moves == 0 {
+            return None;
+        }
+
+        Some(self.total_play_time() / self.timed_moves as u32)
+    }
+
+    pub(crate) fn moves_per_minute(&self) -> f64 {
+        let minutes = self.total_play_time().as_secs_f64() / 60.0;
+        if minutes == 0.0 {
+            return 0.0;
+        }
+
+        self.timed_moves as f64 / minutes
+    }
+}
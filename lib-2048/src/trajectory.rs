@@ -0,0 +1,102 @@
+//! Recording every move of a game driven by a policy
+
+use crate::{
+    GameBoard, GameBoardCells, GameBoardLocation, GameState, MoveDirection,
+    MoveOutcome,
+};
+
+/// A single move recorded in a [`Trajectory`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub direction: MoveDirection,
+    pub outcome: MoveOutcome,
+    /// The board after the move, including any spawned tiles
+    pub cells: GameBoardCells,
+    /// The location and value of each tile spawned after the move
+    pub spawns: Vec<(GameBoardLocation, usize)>,
+}
+
+/// Everything that happened while [simulating](GameBoard::simulate) a game
+#[derive(Debug, Clone)]
+pub struct Trajectory {
+    /// The board before the first move
+    pub initial: GameBoard,
+    pub steps: Vec<Step>,
+    /// The state of the board after the last move
+    pub state: GameState,
+}
+
+impl Trajectory {
+    /// Makes every recorded move again, starting from a copy of the
+    /// [`initial`](Self::initial) board
+    ///
+    /// Each move's tiles spawn where they were recorded, so a game with
+    /// unpredictable spawns replays the same way too. The replayed board's
+    /// random number generator isn't used, so it's left where the initial
+    /// board's was.
+    pub fn replay(&self) -> GameBoard {
+        let mut board = self.initial.clone();
+        for step in &self.steps {
+            let spawns = step.spawns.iter();
+            board.set_spawn_queue(
+                spawns.map(|(loc, value)| (Some(*loc), *value)).collect(),
+            );
+            board.r#move(step.direction);
+        }
+
+        board
+    }
+}
+
+impl GameBoard {
+    /// Plays moves picked by `policy` until it gives up by returning `None`,
+    /// the game is over, or `max_moves` moves have been made, recording
+    /// every move
+    ///
+    /// Moves that don't change the board still count toward `max_moves`.
+    ///
+    /// ```
+    /// use lib_2048::{GameBoard, MoveDirection};
+    ///
+    /// let mut board = GameBoard::new();
+    /// let trajectory = board.simulate(|_| Some(MoveDirection::Left), 5);
+    ///
+    /// assert_eq!(trajectory.steps.len(), 5);
+    /// assert_eq!(trajectory.replay().cells, board.cells);
+    /// ```
+    pub fn simulate(
+        &mut self,
+        mut policy: impl FnMut(&GameBoard) -> Option<MoveDirection>,
+        max_moves: usize,
+    ) -> Trajectory {
+        let initial = self.clone();
+        let mut steps = Vec::new();
+
+        while steps.len() < max_moves && !self.is_over() {
+            let direction = match policy(self) {
+                Some(dir) => dir,
+                None => break,
+            };
+
+            let outcome = self.r#move(direction);
+            let spawns = self
+                .last_spawns
+                .iter()
+                .filter_map(|loc| Some((*loc, self.get(*loc).value()?)))
+                .collect();
+
+            steps.push(Step {
+                direction,
+                outcome,
+                cells: self.cells_owned(),
+                spawns,
+            });
+        }
+
+        Trajectory {
+            initial,
+            steps,
+            state: self.state(),
+        }
+    }
+}